@@ -0,0 +1,108 @@
+//! Compares the generated visitor-based `Deserialize` with deserializing through a temporary
+//! `String`, on JSON where every ID already exists.
+//!
+//! Run with `cargo bench --bench deserialize`.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use msg_interned_id::InternedId;
+use serde::{Deserialize, Deserializer};
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct SpellId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+/// `SpellId` deserialized the way the derive used to: into a `String`, then interned.
+struct StringSpellId(#[allow(dead_code)] SpellId);
+
+impl<'de> Deserialize<'de> for StringSpellId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self(SpellId::new(&s)))
+    }
+}
+
+/// `ItemId` deserialized the way the derive used to.
+struct StringItemId(#[allow(dead_code)] ItemId);
+
+impl<'de> Deserialize<'de> for StringItemId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self(ItemId::new(&s)))
+    }
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Entity {
+    spells: Vec<SpellId>,
+    loot: Vec<ItemId>,
+    health: u32,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct StringEntity {
+    spells: Vec<StringSpellId>,
+    loot: Vec<StringItemId>,
+    health: u32,
+}
+
+/// A scene of 10,000 entities drawing on 100 spells and 100 items.
+fn scene_json() -> String {
+    let entities: Vec<String> = (0..10_000)
+        .map(|i| {
+            format!(
+                r#"{{"spells":["spell_{}","spell_{}"],"loot":["item_{}","item_{}","item_{}"],"health":{}}}"#,
+                i % 100,
+                (i * 7) % 100,
+                i % 100,
+                (i * 3) % 100,
+                (i * 11) % 100,
+                i
+            )
+        })
+        .collect();
+    format!("[{}]", entities.join(","))
+}
+
+fn deserialize_scene(c: &mut Criterion) {
+    let json = scene_json();
+    // Intern every ID up front, as loading the same level twice would.
+    serde_json::from_str::<Vec<Entity>>(&json).unwrap();
+
+    let mut group = c.benchmark_group("deserialize_scene");
+    group.bench_function("visitor", |b| {
+        b.iter(|| serde_json::from_str::<Vec<Entity>>(black_box(&json)).unwrap());
+    });
+    group.bench_function("string", |b| {
+        b.iter(|| serde_json::from_str::<Vec<StringEntity>>(black_box(&json)).unwrap());
+    });
+    group.finish();
+}
+
+/// The same comparison without the surrounding structs: a flat list of 50,000 IDs.
+fn deserialize_ids(c: &mut Criterion) {
+    let ids: Vec<String> = (0..50_000)
+        .map(|i| format!(r#""spell_{}""#, i % 100))
+        .collect();
+    let json = format!("[{}]", ids.join(","));
+    serde_json::from_str::<Vec<SpellId>>(&json).unwrap();
+
+    let mut group = c.benchmark_group("deserialize_ids");
+    group.bench_function("visitor", |b| {
+        b.iter(|| serde_json::from_str::<Vec<SpellId>>(black_box(&json)).unwrap());
+    });
+    group.bench_function("string", |b| {
+        b.iter(|| serde_json::from_str::<Vec<StringSpellId>>(black_box(&json)).unwrap());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, deserialize_scene, deserialize_ids);
+criterion_main!(benches);
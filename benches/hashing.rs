@@ -0,0 +1,67 @@
+//! Compares `IdBuildHasher` with the standard library's default hasher for ID keys.
+//!
+//! Run with `cargo bench --bench hashing`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use msg_interned_id::InternedId;
+use msg_interned_id::collections::IdHashMap;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct SpellId(msg_interned_id::intern::Interned<str>);
+
+fn spell_ids(count: usize) -> Vec<SpellId> {
+    (0..count)
+        .map(|i| SpellId::new(&format!("spell_{i}")))
+        .collect()
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+    for count in [16, 1024] {
+        let ids = spell_ids(count);
+        let default: HashMap<SpellId, usize> = ids.iter().copied().zip(0..).collect();
+        let id_hasher: IdHashMap<SpellId, usize> = ids.iter().copied().zip(0..).collect();
+
+        group.bench_with_input(BenchmarkId::new("default", count), &ids, |b, ids| {
+            b.iter(|| ids.iter().map(|id| default[black_box(id)]).sum::<usize>());
+        });
+        group.bench_with_input(BenchmarkId::new("id_hasher", count), &ids, |b, ids| {
+            b.iter(|| ids.iter().map(|id| id_hasher[black_box(id)]).sum::<usize>());
+        });
+    }
+    group.finish();
+}
+
+fn insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert");
+    for count in [16, 1024] {
+        let ids = spell_ids(count);
+
+        group.bench_with_input(BenchmarkId::new("default", count), &ids, |b, ids| {
+            b.iter(|| {
+                let mut map = HashMap::with_capacity(ids.len());
+                for (value, &id) in ids.iter().enumerate() {
+                    map.insert(black_box(id), value);
+                }
+                map
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("id_hasher", count), &ids, |b, ids| {
+            b.iter(|| {
+                let mut map = IdHashMap::with_capacity_and_hasher(ids.len(), Default::default());
+                for (value, &id) in ids.iter().enumerate() {
+                    map.insert(black_box(id), value);
+                }
+                map
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, lookup, insert);
+criterion_main!(benches);
@@ -8,40 +8,30 @@
 //! Note: This example uses minimal Bevy dependencies and runs without a window,
 //! making it suitable for CI and headless environments.
 
-// Create a facade module to make the generated code work with individual crates
-mod bevy {
-    pub mod ecs {
-        pub mod intern {
-            pub use bevy_ecs::intern::*;
-        }
-    }
-    pub mod reflect {
-        pub use bevy_reflect::*;
-    }
-    pub mod prelude {
-        pub use bevy_ecs::prelude::*;
-        pub use bevy_reflect::prelude::*;
-    }
-}
-
-use bevy::prelude::*;
+use bevy_ecs::prelude::*;
 use bevy_ecs::world::World;
 use msg_interned_id::InternedId;
-use std::collections::HashMap;
+use msg_interned_id::registry::IdRegistry;
+use serde::Deserialize;
 
-// Define various ID types for a game
+// Define various ID types for a game.
+// The example depends on the Bevy sub-crates rather than `bevy` itself, so each ID type
+// tells the derive where to find them.
 
 /// Unique identifier for spells in the game.
 #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct SpellId(bevy::ecs::intern::Interned<str>);
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
 
 /// Unique identifier for items in the player's inventory.
 #[derive(Component, InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct ItemId(bevy::ecs::intern::Interned<str>);
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct ItemId(bevy_ecs::intern::Interned<str>);
 
 /// Unique identifier for enemy types.
 #[derive(Component, InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct EnemyId(bevy::ecs::intern::Interned<str>);
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct EnemyId(bevy_ecs::intern::Interned<str>);
 
 /// Component marking an entity's health.
 #[derive(Component)]
@@ -52,10 +42,11 @@ pub struct Health(pub u32);
 pub struct Damage(pub u32);
 
 /// Data associated with a spell.
+#[derive(Deserialize)]
 pub struct SpellData {
     pub damage: u32,
     pub mana_cost: u32,
-    pub element: &'static str,
+    pub element: String,
 }
 
 fn main() {
@@ -64,7 +55,7 @@ fn main() {
     // Demonstrate basic ID creation and comparison
     demo_basic_ids();
 
-    // Demonstrate using IDs in a HashMap
+    // Demonstrate a registry of spell definitions
     demo_spell_registry();
 
     // Demonstrate pattern matching with deref
@@ -114,50 +105,56 @@ fn demo_basic_ids() {
 }
 
 fn demo_spell_registry() {
-    println!("--- Spell Registry (HashMap) ---");
-
-    let mut spells: HashMap<SpellId, SpellData> = HashMap::new();
-
-    // Register some spells
-    spells.insert(
-        SpellId::new("fireball"),
-        SpellData {
-            damage: 50,
-            mana_cost: 20,
-            element: "fire",
-        },
-    );
-
-    spells.insert(
-        SpellId::new("ice_bolt"),
-        SpellData {
-            damage: 35,
-            mana_cost: 15,
-            element: "ice",
-        },
-    );
+    println!("--- Spell Registry (IdRegistry) ---");
+
+    // Definitions usually come from a content file keyed by ID strings
+    let mut spells: IdRegistry<SpellId, SpellData> = serde_json::from_str(
+        r#"{
+            "fireball": { "damage": 50, "mana_cost": 20, "element": "fire" },
+            "ice_bolt": { "damage": 35, "mana_cost": 15, "element": "ice" }
+        }"#,
+    )
+    .expect("Failed to load spells");
+
+    // Register another spell in code
+    spells
+        .insert(
+            SpellId::new("lightning_strike"),
+            SpellData {
+                damage: 75,
+                mana_cost: 40,
+                element: "lightning".to_string(),
+            },
+        )
+        .expect("lightning_strike is defined once");
+
+    // Defining a spell twice is an error instead of a silent overwrite
+    let duplicate = SpellData {
+        damage: 1,
+        mana_cost: 1,
+        element: "fire".to_string(),
+    };
+    if let Err(err) = spells.insert(SpellId::new("fireball"), duplicate) {
+        println!("Rejected: {err}");
+    }
 
-    spells.insert(
-        SpellId::new("lightning_strike"),
-        SpellData {
-            damage: 75,
-            mana_cost: 40,
-            element: "lightning",
-        },
-    );
+    // The registry is a resource, so systems can read it
+    let mut world = World::new();
+    world.insert_resource(spells);
+    let spells = world.resource::<IdRegistry<SpellId, SpellData>>();
 
     // Look up spells by ID
     let fireball_id = SpellId::new("fireball");
-    if let Some(spell) = spells.get(&fireball_id) {
+    if let Some(spell) = spells.get(fireball_id) {
         println!(
             "Fireball: {} damage, {} mana, {} element",
             spell.damage, spell.mana_cost, spell.element
         );
     }
 
-    // Iterate over all spells
+    // Iterate over all spells, in the order their IDs were created
     println!("\nAll registered spells:");
-    for (id, data) in &spells {
+    for (id, data) in spells {
         println!("  - {}: {} damage", id, data.damage);
     }
 
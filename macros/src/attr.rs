@@ -0,0 +1,683 @@
+//! Parsing of the `#[interned_id(...)]` helper attribute and validation of the derive input.
+
+use quote::ToTokens;
+use syn::Token;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DeriveInput, Fields, GenericArgument, Ident, LitChar, LitInt, LitStr, Path,
+    PathArguments, Type, TypePath,
+};
+
+/// Name of the helper attribute accepted by the derive.
+const ATTR_NAME: &str = "interned_id";
+
+/// Options collected from every `#[interned_id(...)]` attribute on the type.
+pub(crate) struct Options {
+    /// Path to the `msg_interned_id` runtime crate.
+    pub(crate) krate: Path,
+    /// Skip the Bevy reflection impls, the same as `skip(reflect)`.
+    pub(crate) standalone: bool,
+    /// Require `namespace:path` IDs.
+    pub(crate) namespaced: bool,
+    /// Namespace given to IDs constructed from a bare path.
+    pub(crate) default_namespace: Option<LitStr>,
+    /// Separator of hierarchical IDs, if the type is hierarchical.
+    pub(crate) hierarchical: Option<LitChar>,
+    /// Well-known IDs, generated as associated constants.
+    pub(crate) known: Vec<(Ident, LitStr)>,
+    /// Conversions from untrusted strings look IDs up instead of interning them.
+    pub(crate) strict: bool,
+    /// Deserialization looks IDs up instead of interning them; set by `strict` and
+    /// `strict(serde)`.
+    pub(crate) strict_serde: bool,
+    /// Ordering generated as `PartialOrd`/`Ord`, if any.
+    pub(crate) ord: Option<Ordering>,
+    /// Function run on every string before it is interned.
+    pub(crate) validate: Option<Path>,
+    /// Built-in format rules checked before `validate`.
+    pub(crate) format: FormatRules,
+    /// Normalization applied to every string before it is validated and interned.
+    pub(crate) normalize: Normalize,
+    /// Generated impls the user opted out of.
+    pub(crate) skip: Skip,
+    /// Where the deprecated `bevy_ecs` key was given, if it was. Every ID interns through
+    /// the runtime crate's own interner, so the path is parsed but unused.
+    pub(crate) bevy_ecs: Option<proc_macro2::Span>,
+    /// Path to `bevy_reflect` (or the `reflect` module of a Bevy facade).
+    pub(crate) bevy_reflect: Path,
+    /// Path to the `serde` crate.
+    pub(crate) serde: Path,
+    /// What binary serde formats store for each ID.
+    pub(crate) serde_repr: SerdeRepr,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            krate: syn::parse_quote!(::msg_interned_id),
+            standalone: false,
+            namespaced: false,
+            default_namespace: None,
+            hierarchical: None,
+            known: Vec::new(),
+            strict: false,
+            strict_serde: false,
+            ord: None,
+            validate: None,
+            format: FormatRules::default(),
+            normalize: Normalize::default(),
+            skip: Skip::default(),
+            bevy_ecs: None,
+            bevy_reflect: syn::parse_quote!(bevy::reflect),
+            serde: syn::parse_quote!(serde),
+            serde_repr: SerdeRepr::String,
+        }
+    }
+}
+
+impl Options {
+    /// Parse all `#[interned_id(...)]` attributes, reporting every malformed or unknown key.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        let mut seen: Vec<String> = Vec::new();
+        let mut errors: Option<syn::Error> = None;
+        let mut namespaced_span = None;
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident(ATTR_NAME)) {
+            let result = attr.parse_nested_meta(|meta| {
+                let key = meta.path.to_token_stream().to_string().replace(' ', "");
+                if seen.contains(&key) {
+                    return Err(
+                        meta.error(format_args!("duplicate `interned_id` attribute `{key}`"))
+                    );
+                }
+
+                match key.as_str() {
+                    "crate" => options.krate = parse_path_value(&meta)?,
+                    "standalone" => options.standalone = parse_flag(&meta)?,
+                    "namespaced" => {
+                        options.namespaced = parse_flag(&meta)?;
+                        namespaced_span = Some(meta.path.span());
+                    }
+                    "default_namespace" => {
+                        options.default_namespace = Some(parse_namespace(&meta)?);
+                    }
+                    "hierarchical" => options.hierarchical = Some(parse_hierarchical(&meta)?),
+                    "known" => options.known = parse_known(&meta)?,
+                    "strict" => {
+                        (options.strict, options.strict_serde) = parse_strict(&meta)?;
+                    }
+                    "ord" => options.ord = Some(Ordering::parse(&meta)?),
+                    "validate" => options.validate = Some(parse_fn_path_value(&meta)?),
+                    "format" => options.format.case = Some(parse_case_style(&meta)?),
+                    "ascii" => options.format.ascii = parse_flag(&meta)?,
+                    "no_leading_digit" => options.format.no_leading_digit = parse_flag(&meta)?,
+                    "min_len" => options.format.min_len = Some(parse_len(&meta)?),
+                    "max_len" => options.format.max_len = Some(parse_len(&meta)?),
+                    "normalize" => options.normalize = Normalize::parse(&meta)?,
+                    "skip" => options.skip = Skip::parse(&meta)?,
+                    "bevy_ecs" => {
+                        parse_path_value(&meta)?;
+                        options.bevy_ecs = Some(meta.path.span());
+                    }
+                    "bevy_reflect" => options.bevy_reflect = parse_path_value(&meta)?,
+                    "serde" => {
+                        let lit: LitStr = meta.value()?.parse()?;
+                        if SerdeRepr::NAMES.contains(&lit.value().as_str()) {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                format!(
+                                    "`serde` is the path to the serde crate; use \
+                                     `serde_repr = \"{}\"` to pick a representation",
+                                    lit.value()
+                                ),
+                            ));
+                        }
+                        options.serde = parse_path_lit(&lit)?;
+                    }
+                    "serde_repr" => options.serde_repr = SerdeRepr::parse(&meta)?,
+                    _ => {
+                        return Err(
+                            meta.error(format_args!("unknown `interned_id` attribute `{key}`"))
+                        );
+                    }
+                }
+                seen.push(key);
+                Ok(())
+            });
+            if let Err(err) = result {
+                push_error(&mut errors, err);
+            }
+        }
+
+        if let (Some(min), Some(max)) = (&options.format.min_len, &options.format.max_len)
+            && min.base10_parse::<usize>()? > max.base10_parse::<usize>()?
+        {
+            push_error(
+                &mut errors,
+                syn::Error::new(max.span(), "`max_len` must not be smaller than `min_len`"),
+            );
+        }
+
+        if let Some(namespace) = &options.default_namespace
+            && !options.namespaced
+        {
+            push_error(
+                &mut errors,
+                syn::Error::new(
+                    namespace.span(),
+                    "`default_namespace` requires `namespaced`",
+                ),
+            );
+        }
+
+        if let Some(separator) = &options.hierarchical
+            && options.namespaced
+        {
+            push_error(
+                &mut errors,
+                syn::Error::new(
+                    separator.span(),
+                    "`hierarchical` cannot be combined with `namespaced`",
+                ),
+            );
+        }
+
+        // The generated `Default` is the empty string; refuse to emit one that always panics.
+        // Only checked on a clean parse, since an error can hide a later `skip(Default)`.
+        if errors.is_none() && !options.skip.default {
+            let rejected_by = if let Some(span) = namespaced_span {
+                Some((span, "`namespaced`"))
+            } else if let Some(separator) = &options.hierarchical {
+                Some((separator.span(), "`hierarchical`"))
+            } else {
+                match &options.format.min_len {
+                    Some(min) if min.base10_parse::<usize>()? > 0 => {
+                        Some((min.span(), "`min_len`"))
+                    }
+                    _ => None,
+                }
+            };
+            if let Some((span, rule)) = rejected_by {
+                push_error(
+                    &mut errors,
+                    syn::Error::new(
+                        span,
+                        format!(
+                            "{rule} rejects the empty string, which the generated `Default` \
+                             would create; add `skip(Default)`"
+                        ),
+                    ),
+                );
+            }
+        }
+
+        match errors {
+            Some(err) => Err(err),
+            None => Ok(options),
+        }
+    }
+
+    /// Whether the generated constructors have anything to check.
+    pub(crate) fn is_validated(&self) -> bool {
+        self.namespaced
+            || self.hierarchical.is_some()
+            || self.validate.is_some()
+            || !self.format.is_empty()
+    }
+}
+
+/// Rules that become a `msg_interned_id::format::IdFormat` constant.
+#[derive(Default)]
+pub(crate) struct FormatRules {
+    /// `CaseStyle` variant named by `format = "..."`.
+    pub(crate) case: Option<Ident>,
+    pub(crate) ascii: bool,
+    pub(crate) no_leading_digit: bool,
+    pub(crate) min_len: Option<LitInt>,
+    pub(crate) max_len: Option<LitInt>,
+}
+
+impl FormatRules {
+    /// Accepted `format` values and the `CaseStyle` variant each maps to.
+    const CASE_STYLES: &[(&str, &str)] = &[
+        ("snake_case", "Snake"),
+        ("kebab-case", "Kebab"),
+        ("SCREAMING_SNAKE_CASE", "ScreamingSnake"),
+        ("camelCase", "Camel"),
+        ("PascalCase", "Pascal"),
+    ];
+
+    /// The `format` value that names a `CaseStyle` variant, as `CaseStyle::name` would print it.
+    pub(crate) fn case_name(variant: &Ident) -> &'static str {
+        FormatRules::CASE_STYLES
+            .iter()
+            .find(|(_, name)| variant == name)
+            .map_or("", |(style, _)| style)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.case.is_none()
+            && !self.ascii
+            && !self.no_leading_digit
+            && self.min_len.is_none()
+            && self.max_len.is_none()
+    }
+}
+
+/// Parse `format = "snake_case"` into the matching `CaseStyle` variant.
+fn parse_case_style(meta: &ParseNestedMeta<'_>) -> syn::Result<Ident> {
+    let lit: LitStr = meta.value()?.parse()?;
+    let value = lit.value();
+    FormatRules::CASE_STYLES
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, variant)| Ident::new(variant, lit.span()))
+        .ok_or_else(|| {
+            let expected: Vec<String> = FormatRules::CASE_STYLES
+                .iter()
+                .map(|(name, _)| format!("\"{name}\""))
+                .collect();
+            syn::Error::new(
+                lit.span(),
+                format!(
+                    "unknown format `{value}`, expected one of: {}",
+                    expected.join(", ")
+                ),
+            )
+        })
+}
+
+/// How the generated `Ord` compares two IDs.
+pub(crate) enum Ordering {
+    /// By string content, the same in every run.
+    Lexical,
+    /// By dense index, i.e. the order the IDs were first created in.
+    InternOrder,
+}
+
+impl Ordering {
+    /// Accepted `ord` values.
+    const NAMES: &[&str] = &["lexical", "intern_order"];
+
+    /// Parse `ord = "lexical"` or `ord = "intern_order"`.
+    fn parse(meta: &ParseNestedMeta<'_>) -> syn::Result<Self> {
+        let lit: LitStr = meta.value()?.parse()?;
+        match lit.value().as_str() {
+            "lexical" => Ok(Self::Lexical),
+            "intern_order" => Ok(Self::InternOrder),
+            value => {
+                let expected: Vec<String> = Self::NAMES
+                    .iter()
+                    .map(|name| format!("\"{name}\""))
+                    .collect();
+                Err(syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "unknown ordering `{value}`, expected one of: {}",
+                        expected.join(", ")
+                    ),
+                ))
+            }
+        }
+    }
+}
+
+/// How the generated `Serialize`/`Deserialize` represent an ID in binary formats.
+///
+/// Human-readable formats always use the string.
+pub(crate) enum SerdeRepr {
+    String,
+    StableHash,
+    Index,
+}
+
+impl SerdeRepr {
+    /// Accepted `serde_repr` values.
+    const NAMES: &[&str] = &["string", "stable_hash", "index"];
+
+    /// Parse `serde_repr = "string"`, `"stable_hash"` or `"index"`.
+    fn parse(meta: &ParseNestedMeta<'_>) -> syn::Result<Self> {
+        let lit: LitStr = meta.value()?.parse()?;
+        match lit.value().as_str() {
+            "string" => Ok(Self::String),
+            "stable_hash" => Ok(Self::StableHash),
+            "index" => Ok(Self::Index),
+            value => {
+                let expected: Vec<String> = Self::NAMES
+                    .iter()
+                    .map(|name| format!("\"{name}\""))
+                    .collect();
+                Err(syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "unknown serde representation `{value}`, expected one of: {}",
+                        expected.join(", ")
+                    ),
+                ))
+            }
+        }
+    }
+}
+
+/// Parse `default_namespace = "core"`, which must be a non-empty name without `:`.
+fn parse_namespace(meta: &ParseNestedMeta<'_>) -> syn::Result<LitStr> {
+    let lit: LitStr = meta.value()?.parse()?;
+    let value = lit.value();
+    if value.is_empty() || value.contains(':') {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("`{value}` is not a valid namespace: it must be non-empty and contain no `:`"),
+        ));
+    }
+    Ok(lit)
+}
+
+/// Parse a bare `hierarchical` or `hierarchical(separator = "/")` into the separator.
+fn parse_hierarchical(meta: &ParseNestedMeta<'_>) -> syn::Result<LitChar> {
+    if !meta.input.peek(syn::token::Paren) {
+        parse_flag(meta)?;
+        return Ok(LitChar::new('.', meta.path.span()));
+    }
+
+    let mut separator = None;
+    meta.parse_nested_meta(|item| {
+        if !item.path.is_ident("separator") {
+            let name = item.path.to_token_stream().to_string().replace(' ', "");
+            return Err(item.error(format_args!(
+                "unknown `hierarchical` option `{name}`, expected `separator`"
+            )));
+        }
+        if separator.is_some() {
+            return Err(item.error("duplicate `hierarchical` option `separator`"));
+        }
+        let lit: LitStr = item.value()?.parse()?;
+        let value = lit.value();
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if !c.is_alphanumeric() && !c.is_whitespace() => {
+                separator = Some(LitChar::new(c, lit.span()));
+                Ok(())
+            }
+            _ => Err(syn::Error::new(
+                lit.span(),
+                format!("`{value}` is not a valid separator: expected one punctuation character"),
+            )),
+        }
+    })?;
+    separator.ok_or_else(|| meta.error("expected `hierarchical(separator = \"...\")`"))
+}
+
+/// Parse `known(NAME = "value", ...)`, rejecting repeated names.
+fn parse_known(meta: &ParseNestedMeta<'_>) -> syn::Result<Vec<(Ident, LitStr)>> {
+    let mut known: Vec<(Ident, LitStr)> = Vec::new();
+    meta.parse_nested_meta(|item| {
+        let Some(name) = item.path.get_ident() else {
+            return Err(item.error("expected a constant name, e.g. `FIREBALL = \"fireball\"`"));
+        };
+        if known.iter().any(|(existing, _)| existing == name) {
+            return Err(item.error(format_args!("`{name}` is already declared")));
+        }
+        let value: LitStr = item.value()?.parse()?;
+        known.push((name.clone(), value));
+        Ok(())
+    })?;
+    Ok(known)
+}
+
+/// Parse `strict` (every string conversion) or `strict(serde)` (deserialization only), as
+/// `(strict, strict_serde)`.
+fn parse_strict(meta: &ParseNestedMeta<'_>) -> syn::Result<(bool, bool)> {
+    if !meta.input.peek(syn::token::Paren) {
+        return parse_flag(meta).map(|strict| (strict, strict));
+    }
+    let mut serde = false;
+    meta.parse_nested_meta(|item| {
+        let name = item.path.to_token_stream().to_string().replace(' ', "");
+        if name != "serde" {
+            return Err(item.error(format_args!(
+                "unknown `strict` option `{name}`, expected `serde`"
+            )));
+        }
+        if serde {
+            return Err(item.error("`serde` is already listed"));
+        }
+        serde = true;
+        Ok(())
+    })?;
+    if !serde {
+        return Err(meta.error("expected `strict` or `strict(serde)`"));
+    }
+    Ok((false, true))
+}
+
+/// Parse `key = 64` as a byte length.
+fn parse_len(meta: &ParseNestedMeta<'_>) -> syn::Result<LitInt> {
+    let lit: LitInt = meta.value()?.parse()?;
+    lit.base10_parse::<usize>()?;
+    Ok(lit)
+}
+
+/// Steps that become a `msg_interned_id::normalize::Normalization` constant.
+#[derive(Default)]
+pub(crate) struct Normalize {
+    pub(crate) trim: bool,
+    pub(crate) lowercase: bool,
+    pub(crate) nfc: bool,
+}
+
+impl Normalize {
+    /// Names accepted inside `normalize(...)`, in the order they are applied.
+    const NAMES: &[&str] = &["trim", "lowercase", "nfc"];
+
+    /// Parse the `normalize(...)` list, rejecting unknown and repeated steps.
+    fn parse(meta: &ParseNestedMeta<'_>) -> syn::Result<Self> {
+        let mut normalize = Self::default();
+        meta.parse_nested_meta(|item| {
+            let name = item.path.to_token_stream().to_string().replace(' ', "");
+            let flag = match name.as_str() {
+                "trim" => &mut normalize.trim,
+                "lowercase" => &mut normalize.lowercase,
+                "nfc" => &mut normalize.nfc,
+                _ => {
+                    return Err(item.error(format_args!(
+                        "unknown `normalize` step `{name}`, expected one of: {}",
+                        Self::NAMES.join(", ")
+                    )));
+                }
+            };
+            if *flag {
+                return Err(item.error(format_args!("`{name}` is already listed")));
+            }
+            *flag = true;
+            Ok(())
+        })?;
+        Ok(normalize)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        !self.trim && !self.lowercase && !self.nfc
+    }
+}
+
+/// Generated impls that can be turned off with `#[interned_id(skip(...))]`.
+#[derive(Default)]
+pub(crate) struct Skip {
+    pub(crate) display: bool,
+    pub(crate) from: bool,
+    pub(crate) from_str: bool,
+    pub(crate) deref: bool,
+    pub(crate) default: bool,
+    pub(crate) serde: bool,
+    pub(crate) reflect: bool,
+}
+
+impl Skip {
+    /// Names accepted inside `skip(...)`, in the order they are listed in diagnostics.
+    const NAMES: &[&str] = &[
+        "Display", "From", "FromStr", "Deref", "Default", "serde", "reflect",
+    ];
+
+    /// Parse the `skip(...)` list, rejecting unknown and repeated names.
+    fn parse(meta: &ParseNestedMeta<'_>) -> syn::Result<Self> {
+        let mut skip = Self::default();
+        meta.parse_nested_meta(|item| {
+            let name = item.path.to_token_stream().to_string().replace(' ', "");
+            let flag = match name.as_str() {
+                "Display" => &mut skip.display,
+                "From" => &mut skip.from,
+                "FromStr" => &mut skip.from_str,
+                "Deref" => &mut skip.deref,
+                "Default" => &mut skip.default,
+                "serde" => &mut skip.serde,
+                "reflect" => &mut skip.reflect,
+                _ => {
+                    return Err(item.error(format_args!(
+                        "unknown `skip` item `{name}`, expected one of: {}",
+                        Self::NAMES.join(", ")
+                    )));
+                }
+            };
+            if *flag {
+                return Err(item.error(format_args!("`{name}` is already skipped")));
+            }
+            *flag = true;
+            Ok(())
+        })?;
+        Ok(skip)
+    }
+}
+
+/// Accept a bare `key` with no value or nested list.
+fn parse_flag(meta: &ParseNestedMeta<'_>) -> syn::Result<bool> {
+    if meta.input.is_empty() || meta.input.peek(Token![,]) {
+        Ok(true)
+    } else {
+        let key = meta.path.to_token_stream().to_string();
+        Err(meta.error(format_args!("`{key}` does not take a value")))
+    }
+}
+
+/// Parse `key = path::to::item`, also accepting the path as a string literal.
+fn parse_fn_path_value(meta: &ParseNestedMeta<'_>) -> syn::Result<Path> {
+    let value = meta.value()?;
+    if value.peek(LitStr) {
+        let lit: LitStr = value.parse()?;
+        lit.parse().map_err(|_| {
+            syn::Error::new(lit.span(), format!("`{}` is not a valid path", lit.value()))
+        })
+    } else {
+        value.parse()
+    }
+}
+
+/// Parse `key = "some::path"` into a module path, spanned at the string literal.
+fn parse_path_value(meta: &ParseNestedMeta<'_>) -> syn::Result<Path> {
+    parse_path_lit(&meta.value()?.parse()?)
+}
+
+/// Parse a string literal holding a module path.
+fn parse_path_lit(lit: &LitStr) -> syn::Result<Path> {
+    lit.parse_with(Path::parse_mod_style)
+        .map_err(|_| syn::Error::new(lit.span(), format!("`{}` is not a valid path", lit.value())))
+}
+
+/// Check that the input is a non-generic tuple struct wrapping exactly one `Interned<str>`.
+///
+/// Returns the field's type path without generic arguments, which doubles as the tuple
+/// struct constructor for the wrapped `Interned` value.
+pub(crate) fn validate_shape(input: &DeriveInput) -> syn::Result<Path> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`InternedId` cannot be derived for generic types",
+        ));
+    }
+
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        Data::Enum(data) => {
+            return Err(syn::Error::new(
+                data.enum_token.span,
+                "`InternedId` can only be derived for tuple structs, not enums",
+            ));
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "`InternedId` can only be derived for tuple structs, not unions",
+            ));
+        }
+    };
+
+    let fields = match &data.fields {
+        Fields::Unnamed(fields) => fields,
+        Fields::Named(fields) => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                "`InternedId` requires a tuple struct, e.g. `struct SpellId(Interned<str>);`",
+            ));
+        }
+        Fields::Unit => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`InternedId` requires a tuple struct with one `Interned<str>` field",
+            ));
+        }
+    };
+
+    let mut iter = fields.unnamed.iter();
+    let Some(field) = iter.next() else {
+        return Err(syn::Error::new_spanned(
+            fields,
+            "`InternedId` requires exactly one `Interned<str>` field",
+        ));
+    };
+    if let Some(extra) = iter.next() {
+        return Err(syn::Error::new_spanned(
+            extra,
+            "`InternedId` requires exactly one field; unexpected extra field",
+        ));
+    }
+
+    interned_str_constructor(&field.ty).ok_or_else(|| {
+        syn::Error::new_spanned(
+            &field.ty,
+            "expected `Interned<str>`, e.g. `bevy::ecs::intern::Interned<str>`",
+        )
+    })
+}
+
+/// If `ty` is a path type whose last segment is `Interned<str>`, return it without `<str>`.
+fn interned_str_constructor(ty: &Type) -> Option<Path> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let last = path.segments.last()?;
+    if last.ident != "Interned" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    let is_str = matches!(
+        args.args.iter().collect::<Vec<_>>().as_slice(),
+        [GenericArgument::Type(Type::Path(inner))] if inner.qself.is_none() && inner.path.is_ident("str")
+    );
+    if !is_str {
+        return None;
+    }
+
+    let mut constructor = path.clone();
+    constructor.segments.last_mut()?.arguments = PathArguments::None;
+    Some(constructor)
+}
+
+/// Accumulate `err` into `errors` so every problem is reported in a single compile.
+pub(crate) fn push_error(errors: &mut Option<syn::Error>, err: syn::Error) {
+    match errors {
+        Some(existing) => existing.combine(err),
+        None => *errors = Some(err),
+    }
+}
@@ -0,0 +1,1288 @@
+//! Procedural macro implementation for [`msg_interned_id`].
+//!
+//! This crate is an implementation detail: depend on `msg_interned_id`, which re-exports the
+//! `InternedId` derive together with the runtime support the generated code relies on.
+//!
+//! [`msg_interned_id`]: https://docs.rs/msg_interned_id
+
+mod attr;
+mod rules;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{DeriveInput, Ident, Path, parse_macro_input};
+
+#[cfg(feature = "serde")]
+use crate::attr::SerdeRepr;
+use crate::attr::{Options, Ordering};
+
+/// Generate the interner and basic methods for an ID type.
+///
+/// Every ID interns through the runtime crate's own interner. The field is built from the
+/// interned `&'static str` through `field_ctor`, so any `Interned<str>` type works as the
+/// field.
+fn generate_core_impl(
+    name: &Ident,
+    name_str: &str,
+    interner_name: &Ident,
+    field_ctor: &Path,
+    options: &Options,
+) -> TokenStream2 {
+    let krate = &options.krate;
+    let cow = quote!(std::borrow::Cow);
+    let normalize = generate_normalize(options);
+
+    let constructors = if options.is_validated() {
+        let namespace_check = generate_namespace_check(name_str, options);
+        let hierarchy_check = options.hierarchical.as_ref().map(|separator| {
+            quote! {
+                #krate::hierarchy::check(id, #separator)
+                    .map_err(|err| #krate::IdError::hierarchy(#name_str, id, err))?;
+            }
+        });
+        let format_check = generate_format_check(name_str, options);
+        let custom_check = options.validate.as_ref().map(|validator| {
+            quote! {
+                #validator(id).map_err(|reason| #krate::IdError::rejected(#name_str, id, reason))?;
+            }
+        });
+
+        quote! {
+            /// Create a new ID from a string, rejecting it if validation fails.
+            /// Rejected strings are never interned.
+            pub fn try_new(id: &str) -> Result<Self, #krate::IdError> {
+                Self::__msg_interned_id_prepare(id)
+                    .map(|id| Self::__msg_interned_id_intern_unchecked(&id))
+            }
+
+            /// Create a new ID from a string.
+            /// The string is interned for efficient comparison.
+            ///
+            /// # Panics
+            ///
+            /// Panics if validation rejects the string; use `try_new` to handle that case.
+            #[must_use]
+            pub fn new(id: &str) -> Self {
+                match Self::try_new(id) {
+                    Ok(id) => id,
+                    Err(err) => panic!("{err}"),
+                }
+            }
+
+            /// Create a new ID from a `'static` string, rejecting it if validation fails.
+            /// Unless normalization changes it, the string is interned without being copied.
+            pub fn try_from_static(id: &'static str) -> Result<Self, #krate::IdError> {
+                Self::__msg_interned_id_prepare(id).map(Self::__msg_interned_id_intern_prepared)
+            }
+
+            /// Create a new ID from a `'static` string, such as a literal.
+            /// Unless normalization changes it, the string is interned without being copied.
+            ///
+            /// # Panics
+            ///
+            /// Panics if validation rejects the string; use `try_from_static` to handle that
+            /// case.
+            #[must_use]
+            pub fn from_static(id: &'static str) -> Self {
+                match Self::try_from_static(id) {
+                    Ok(id) => id,
+                    Err(err) => panic!("{err}"),
+                }
+            }
+
+            /// Normalize and validate `id`, borrowing it if it needs no changes.
+            fn __msg_interned_id_prepare(id: &str) -> Result<#cow<'_, str>, #krate::IdError> {
+                let id = #normalize;
+                #namespace_check
+                {
+                    let id: &str = &id;
+                    #hierarchy_check
+                    #format_check
+                    #custom_check
+                }
+                Ok(id)
+            }
+        }
+    } else {
+        quote! {
+            /// Create a new ID from a string.
+            /// The string is interned for efficient comparison.
+            #[must_use]
+            pub fn new(id: &str) -> Self {
+                Self::__msg_interned_id_intern_unchecked(&Self::__msg_interned_id_prepare(id))
+            }
+
+            /// Create a new ID from a `'static` string, such as a literal.
+            /// Unless normalization changes it, the string is interned without being copied.
+            #[must_use]
+            pub fn from_static(id: &'static str) -> Self {
+                Self::__msg_interned_id_intern_prepared(Self::__msg_interned_id_prepare(id))
+            }
+
+            /// Normalize `id`, borrowing it if it needs no changes.
+            fn __msg_interned_id_prepare(id: &str) -> #cow<'_, str> {
+                #normalize
+            }
+        }
+    };
+
+    let prepared = if options.is_validated() {
+        quote!(Self::__msg_interned_id_prepare(id).ok()?)
+    } else {
+        quote!(Self::__msg_interned_id_prepare(id))
+    };
+
+    let declared = (!options.known.is_empty())
+        .then(|| quote!(.or_else(|| Self::__msg_interned_id_get_known(&id))));
+    let intern_declared = (!options.known.is_empty()).then(|| {
+        quote! {
+            for (_, value) in Self::KNOWN {
+                let _ = Self::get(value);
+            }
+        }
+    });
+    let suggest_query = if options.is_validated() {
+        quote!(Self::__msg_interned_id_prepare(query).unwrap_or(#cow::Borrowed(query)))
+    } else {
+        quote!(Self::__msg_interned_id_prepare(query))
+    };
+
+    let namespace_accessors = options.namespaced.then(|| {
+        quote! {
+            /// The namespace of this ID, e.g. `"core"` for `core:fireball`.
+            #[must_use]
+            pub fn namespace(&self) -> &'static str {
+                #krate::namespace::split(self.as_str()).0
+            }
+
+            /// The path of this ID within its namespace, e.g. `"fireball"` for `core:fireball`.
+            #[must_use]
+            pub fn path(&self) -> &'static str {
+                #krate::namespace::split(self.as_str()).1
+            }
+        }
+    });
+
+    let hierarchy_methods = options.hierarchical.as_ref().map(|separator| {
+        quote! {
+            /// The ID one level up, e.g. `buff.speed` for `buff.speed.minor`, or `None` for
+            /// a top-level ID.
+            ///
+            /// The parent goes through the same validation as `try_new`, so this also returns
+            /// `None` if the type rejects it, e.g. with a `min_len` that only full IDs reach.
+            /// Like `child`, a valid parent is interned if it does not exist yet.
+            #[must_use]
+            pub fn parent(&self) -> Option<Self> {
+                let parent = #krate::hierarchy::parent(self.as_str(), #separator)?;
+                Self::__msg_interned_id_prepare(parent)
+                    .ok()
+                    .map(Self::__msg_interned_id_intern_prepared)
+            }
+
+            /// The segments of this ID from the top down.
+            pub fn segments(&self) -> std::str::Split<'static, char> {
+                self.as_str().split(#separator)
+            }
+
+            /// Number of segments, `1` for a top-level ID.
+            #[must_use]
+            pub fn depth(&self) -> usize {
+                #krate::hierarchy::depth(self.as_str(), #separator)
+            }
+
+            /// Whether this ID lies strictly below `ancestor`.
+            #[must_use]
+            pub fn is_descendant_of(&self, ancestor: &Self) -> bool {
+                #krate::hierarchy::is_descendant(self.as_str(), ancestor.as_str(), #separator)
+            }
+
+            /// The ID one level below this one, named `segment`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if validation rejects the resulting ID.
+            #[must_use]
+            pub fn child(&self, segment: &str) -> Self {
+                Self::new(&format!("{}{}{segment}", self.as_str(), #separator))
+            }
+        }
+    });
+
+    let known = generate_known_items(name, options);
+
+    quote! {
+        static #interner_name: #krate::intern::Interner = #krate::intern::Interner::new();
+
+        impl #name {
+            #constructors
+
+            /// Get the string value of this ID.
+            /// Returns the interned static string.
+            #[must_use]
+            pub fn as_str(&self) -> &'static str {
+                self.0.0
+            }
+
+            /// Look up the existing ID for `id` without interning it.
+            ///
+            /// Returns `None` if no ID with this value has been created yet, so untrusted
+            /// input can be checked without growing the interner. IDs declared with
+            /// `known(...)` count as created even before their first access.
+            #[must_use]
+            pub fn get(id: &str) -> Option<Self> {
+                let id = #prepared;
+                #interner_name
+                    .get(&id)
+                    .map(|interned| Self(#field_ctor(interned.0)))
+                    #declared
+            }
+
+            /// Up to `n` existing IDs closest to `query` by edit distance, nearest first, for
+            /// "did you mean" hints. Includes IDs declared with `known(...)`; never interns
+            /// `query`. See `msg_interned_id::suggest::closest` for the ranking.
+            #[must_use]
+            pub fn suggest(query: &str, n: usize) -> Vec<Self> {
+                #intern_declared
+                let query = #suggest_query;
+                #krate::suggest::closest(&query, Self::all(), n)
+            }
+
+            /// Whether an ID with this value has already been created.
+            #[must_use]
+            pub fn contains(id: &str) -> bool {
+                Self::get(id).is_some()
+            }
+
+            /// Every ID created so far, in creation order.
+            ///
+            /// Safe to call while other threads create IDs; those may or may not be included.
+            pub fn all() -> impl Iterator<Item = Self> {
+                #interner_name
+                    .iter()
+                    .map(|interned| Self(#field_ctor(interned.0)))
+            }
+
+            /// Number of distinct IDs created so far.
+            #[must_use]
+            pub fn count() -> usize {
+                #interner_name.len()
+            }
+
+            /// Dense index of this ID: `0` for the first ID created, `1` for the next, and so
+            /// on. Stable for the rest of the program; lock-free and O(1).
+            ///
+            /// # Panics
+            ///
+            /// Panics if the ID was not created through this type's constructors.
+            #[must_use]
+            pub fn index(&self) -> u32 {
+                #interner_name
+                    .index_of(self.as_str())
+                    .expect(concat!("`", #name_str, "` was not created through its interner"))
+            }
+
+            /// Hash of the string content, the same on every run and machine, so it can go
+            /// into replay checksums and network messages. The hash is computed once when the
+            /// ID is interned, and each call is a lock-free lookup of that value by the ID's
+            /// address; see `msg_interned_id::hash::stable_hash` for the algorithm.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the ID was not created through this type's constructors.
+            #[must_use]
+            pub fn stable_hash(&self) -> u64 {
+                #interner_name
+                    .stable_hash_of(self.as_str())
+                    .expect(concat!("`", #name_str, "` was not created through its interner"))
+            }
+
+            /// The existing ID whose `stable_hash()` is `hash`, for decoding hashes received
+            /// from another process. Never interns anything.
+            ///
+            /// Returns `None` if no ID has this hash yet, or if several IDs share it.
+            #[must_use]
+            pub fn from_stable_hash(hash: u64) -> Option<Self> {
+                #interner_name
+                    .by_stable_hash(hash)
+                    .map(|interned| Self(#field_ctor(interned.0)))
+            }
+
+            /// The ID with the given `index`, if that many IDs have been created.
+            /// Lock-free and O(1).
+            #[must_use]
+            pub fn from_index(index: u32) -> Option<Self> {
+                #interner_name
+                    .by_index(index)
+                    .map(|interned| Self(#field_ctor(interned.0)))
+            }
+
+            #known
+            #namespace_accessors
+            #hierarchy_methods
+
+            /// Intern `id` without running any validation.
+            fn __msg_interned_id_intern_unchecked(id: &str) -> Self {
+                Self(#field_ctor(#interner_name.intern(id).0))
+            }
+
+            /// Intern an already prepared `id`, registering it without a copy if it is
+            /// still the caller's `'static` string.
+            fn __msg_interned_id_intern_prepared(id: #cow<'static, str>) -> Self {
+                match id {
+                    #cow::Borrowed(id) => Self(#field_ctor(#interner_name.intern_static(id).0)),
+                    #cow::Owned(id) => Self::__msg_interned_id_intern_unchecked(&id),
+                }
+            }
+        }
+    }
+}
+
+/// Generate an associated constant per well-known ID, plus the `KNOWN` table.
+fn generate_known_items(name: &Ident, options: &Options) -> Option<TokenStream2> {
+    if options.known.is_empty() {
+        return None;
+    }
+
+    let krate = &options.krate;
+    let consts = options.known.iter().map(|(ident, value)| {
+        let doc = format!(
+            "The well-known ID `{:?}`, interned on first access.",
+            value.value()
+        );
+        quote! {
+            #[doc = #doc]
+            pub const #ident: #krate::known::Known<Self> = #krate::known::Known::new(|| {
+                static ID: std::sync::OnceLock<#name> = std::sync::OnceLock::new();
+                ID.get_or_init(|| #name::from_static(#value))
+            });
+        }
+    });
+    let table = options.known.iter().map(|(ident, value)| {
+        let ident_str = ident.to_string();
+        quote!((#ident_str, #value))
+    });
+
+    let matches = options.known.iter().map(|(ident, value)| {
+        let equal = if options.is_validated() {
+            quote!(Self::__msg_interned_id_prepare(#value).is_ok_and(|value| value == id))
+        } else {
+            quote!(Self::__msg_interned_id_prepare(#value) == id)
+        };
+        quote! {
+            if #equal {
+                return Some(Self::#ident.get());
+            }
+        }
+    });
+
+    Some(quote! {
+        #(#consts)*
+
+        /// The well-known ID whose prepared value is `id`, interning it on first access.
+        fn __msg_interned_id_get_known(id: &str) -> Option<Self> {
+            #(#matches)*
+            None
+        }
+
+        /// Every well-known ID as `(constant name, value)`, in declaration order.
+        pub const KNOWN: &'static [(&'static str, &'static str)] = &[#(#table),*];
+    })
+}
+
+/// Generate the expression normalizing `id` into a `Cow<str>`.
+fn generate_normalize(options: &Options) -> TokenStream2 {
+    let steps = &options.normalize;
+    if steps.is_empty() {
+        return quote!(std::borrow::Cow::Borrowed(id));
+    }
+
+    let krate = &options.krate;
+    let trim = steps.trim.then(|| quote!(.trim()));
+    let lowercase = steps.lowercase.then(|| quote!(.lowercase()));
+    let nfc = steps.nfc.then(|| quote!(.nfc()));
+
+    quote! {{
+        const NORMALIZE: #krate::normalize::Normalization =
+            #krate::normalize::Normalization::new() #trim #lowercase #nfc;
+        NORMALIZE.apply(id)
+    }}
+}
+
+/// Generate the statement resolving `id` to its full `namespace:path` form, if namespaced.
+fn generate_namespace_check(name_str: &str, options: &Options) -> Option<TokenStream2> {
+    if !options.namespaced {
+        return None;
+    }
+
+    let krate = &options.krate;
+    let default = match &options.default_namespace {
+        Some(namespace) => quote!(Some(#namespace)),
+        None => quote!(None),
+    };
+
+    // A borrowed result is `id` itself, so keep the original `Cow` and its lifetime.
+    Some(quote! {
+        let id = match #krate::namespace::resolve(&id, #default) {
+            Ok(std::borrow::Cow::Borrowed(_)) => id,
+            Ok(std::borrow::Cow::Owned(resolved)) => std::borrow::Cow::Owned(resolved),
+            Err(err) => return Err(#krate::IdError::namespace(#name_str, &id, err)),
+        };
+    })
+}
+
+/// Generate the statement checking `id` against the built-in format rules, if any are set.
+fn generate_format_check(name_str: &str, options: &Options) -> Option<TokenStream2> {
+    let rules = &options.format;
+    if rules.is_empty() {
+        return None;
+    }
+
+    let krate = &options.krate;
+    let case = rules
+        .case
+        .as_ref()
+        .map(|style| quote!(.case(#krate::format::CaseStyle::#style)));
+    let ascii = rules.ascii.then(|| quote!(.ascii()));
+    let no_leading_digit = rules.no_leading_digit.then(|| quote!(.no_leading_digit()));
+    let min_len = rules.min_len.as_ref().map(|len| quote!(.min_len(#len)));
+    let max_len = rules.max_len.as_ref().map(|len| quote!(.max_len(#len)));
+
+    // Namespaced and hierarchical IDs check each part separately, not the separator.
+    let check = if options.namespaced {
+        quote!(check_segments(id, #krate::namespace::SEPARATOR))
+    } else if let Some(separator) = &options.hierarchical {
+        quote!(check_segments(id, #separator))
+    } else {
+        quote!(check(id))
+    };
+
+    Some(quote! {
+        const FORMAT: #krate::format::IdFormat = #krate::format::IdFormat::new()
+            #case #ascii #no_leading_digit #min_len #max_len;
+        FORMAT
+            .#check
+            .map_err(|err| #krate::IdError::format(#name_str, id, err))?;
+    })
+}
+
+/// Generate the runtime `InternedId` trait implementation, forwarding to the inherent methods.
+fn generate_trait_impl(name: &Ident, name_str: &str, options: &Options) -> TokenStream2 {
+    let krate = &options.krate;
+    let parse = if options.strict_serde {
+        generate_strict_lookup(name_str, options)
+    } else if options.is_validated() {
+        quote!(Self::try_new(id))
+    } else {
+        quote!(Ok(Self::new(id)))
+    };
+
+    let intern_known = (!options.known.is_empty()).then(|| {
+        let idents = options.known.iter().map(|(ident, _)| ident);
+        quote! {
+            fn intern_known() {
+                #(let _ = Self::#idents.get();)*
+            }
+        }
+    });
+
+    quote! {
+        impl #krate::InternedId for #name {
+            const TYPE_NAME: &'static str = #name_str;
+
+            fn as_str(&self) -> &'static str {
+                #name::as_str(self)
+            }
+
+            fn index(&self) -> u32 {
+                #name::index(self)
+            }
+
+            fn stable_hash(&self) -> u64 {
+                #name::stable_hash(self)
+            }
+
+            fn from_index(index: u32) -> Option<Self> {
+                #name::from_index(index)
+            }
+
+            fn get(id: &str) -> Option<Self> {
+                #name::get(id)
+            }
+
+            fn count() -> usize {
+                #name::count()
+            }
+
+            fn parse(id: &str) -> Result<Self, #krate::IdError> {
+                #parse
+            }
+
+            #intern_known
+        }
+    }
+}
+
+/// Generate the lookup of `id` done by strict deserialization, failing with the closest
+/// existing IDs as suggestions.
+fn generate_strict_lookup(name_str: &str, options: &Options) -> TokenStream2 {
+    let krate = &options.krate;
+    quote! {
+        Self::get(id).ok_or_else(|| {
+            let suggestions = Self::suggest(id, 3).into_iter().map(|id| id.as_str());
+            #krate::IdError::unknown(#name_str, id).with_suggestions(suggestions)
+        })
+    }
+}
+
+/// Generate the `Display` implementation.
+fn generate_display_impl(name: &Ident) -> TokenStream2 {
+    quote! {
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.as_str())
+            }
+        }
+    }
+}
+
+/// Generate `PartialOrd` and `Ord` implementations for `ord = "..."`.
+///
+/// Equal IDs share a pointer, so the comparison only inspects the strings or indices of IDs
+/// that differ, and stays consistent with the pointer-based `Eq`.
+fn generate_ord_impl(name: &Ident, ordering: &Ordering) -> TokenStream2 {
+    let compare = match ordering {
+        Ordering::Lexical => quote! { self.as_str().cmp(other.as_str()) },
+        Ordering::InternOrder => quote! { self.index().cmp(&other.index()) },
+    };
+
+    quote! {
+        impl std::cmp::PartialOrd for #name {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(std::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl std::cmp::Ord for #name {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                if std::ptr::eq(self.as_str(), other.as_str()) {
+                    return std::cmp::Ordering::Equal;
+                }
+                #compare
+            }
+        }
+    }
+}
+
+/// Generate `From<&str>` and `From<String>` implementations, or lookup-only `TryFrom`
+/// implementations for strict IDs.
+fn generate_from_impls(name: &Ident, name_str: &str, options: &Options) -> TokenStream2 {
+    if options.strict {
+        let krate = &options.krate;
+        return quote! {
+            impl TryFrom<&str> for #name {
+                type Error = #krate::IdError;
+
+                fn try_from(s: &str) -> Result<Self, Self::Error> {
+                    Self::get(s).ok_or_else(|| #krate::IdError::unknown(#name_str, s))
+                }
+            }
+
+            impl TryFrom<String> for #name {
+                type Error = #krate::IdError;
+
+                fn try_from(s: String) -> Result<Self, Self::Error> {
+                    Self::try_from(s.as_str())
+                }
+            }
+        };
+    }
+
+    quote! {
+        impl From<&str> for #name {
+            fn from(s: &str) -> Self {
+                Self::new(s)
+            }
+        }
+
+        impl From<String> for #name {
+            fn from(s: String) -> Self {
+                Self::new(&s)
+            }
+        }
+    }
+}
+
+/// Generate the `FromStr` implementation, which fails only if the ID is validated or strict.
+fn generate_from_str_impl(name: &Ident, name_str: &str, options: &Options) -> TokenStream2 {
+    let krate = &options.krate;
+    if options.strict {
+        quote! {
+            impl std::str::FromStr for #name {
+                type Err = #krate::IdError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Self::get(s).ok_or_else(|| #krate::IdError::unknown(#name_str, s))
+                }
+            }
+        }
+    } else if options.is_validated() {
+        quote! {
+            impl std::str::FromStr for #name {
+                type Err = #krate::IdError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Self::try_new(s)
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl std::str::FromStr for #name {
+                type Err = std::convert::Infallible;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Ok(Self::new(s))
+                }
+            }
+        }
+    }
+}
+
+/// Generate the `Deref<Target = str>` implementation.
+fn generate_deref_impl(name: &Ident) -> TokenStream2 {
+    quote! {
+        impl std::ops::Deref for #name {
+            type Target = str;
+
+            fn deref(&self) -> &Self::Target {
+                self.0.0
+            }
+        }
+    }
+}
+
+/// Generate the `Default` implementation (the empty string).
+///
+/// Attribute parsing rejects types whose built-in rules reject the empty string, so this
+/// only panics if a custom `validate` function does.
+fn generate_default_impl(name: &Ident) -> TokenStream2 {
+    quote! {
+        impl Default for #name {
+            fn default() -> Self {
+                Self::new("")
+            }
+        }
+    }
+}
+
+/// Generate serde serialization implementations.
+///
+/// Validated IDs report rejected strings as deserialization errors instead of panicking, and
+/// strict IDs report strings that are not already interned, suggesting the closest ones.
+/// With a compact `serde_repr`, binary formats store the stable hash or index instead, which
+/// must name an existing ID.
+#[cfg(feature = "serde")]
+fn generate_serde_impls(name: &Ident, name_str: &str, options: &Options) -> TokenStream2 {
+    let serde = &options.serde;
+    let construct = if options.strict_serde {
+        // Shares the lookup and its suggestions with `InternedId::parse`.
+        let krate = &options.krate;
+        quote!(<#name as #krate::InternedId>::parse(s).map_err(#serde::de::Error::custom))
+    } else if options.is_validated() {
+        quote!(#name::try_new(s).map_err(#serde::de::Error::custom))
+    } else {
+        quote!(Ok(#name::new(s)))
+    };
+
+    // A visitor sees borrowed, transient and owned strings alike as `&str`, so reading an ID
+    // that already exists allocates nothing.
+    let deserialize_str = quote! {
+        struct IdVisitor;
+
+        impl<'de> #serde::de::Visitor<'de> for IdVisitor {
+            type Value = #name;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(concat!("a ", #name_str, " string"))
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: #serde::de::Error,
+            {
+                #construct
+            }
+        }
+
+        deserializer.deserialize_str(IdVisitor)
+    };
+
+    let compact = match options.serde_repr {
+        SerdeRepr::String => None,
+        SerdeRepr::StableHash => Some((
+            quote!(serializer.serialize_u64(self.stable_hash())),
+            quote! {
+                let hash = <u64 as #serde::Deserialize>::deserialize(deserializer)?;
+                #name::from_stable_hash(hash).ok_or_else(|| {
+                    #serde::de::Error::custom(format_args!(
+                        "no {} with stable hash {:#018x} exists",
+                        #name_str,
+                        hash
+                    ))
+                })
+            },
+        )),
+        SerdeRepr::Index => Some((
+            quote!(serializer.serialize_u32(self.index())),
+            quote! {
+                let index = <u32 as #serde::Deserialize>::deserialize(deserializer)?;
+                #name::from_index(index).ok_or_else(|| {
+                    #serde::de::Error::custom(format_args!(
+                        "no {} with index {} exists",
+                        #name_str,
+                        index
+                    ))
+                })
+            },
+        )),
+    };
+
+    let (serialize_body, deserialize_body) = match compact {
+        None => (
+            quote!(serializer.serialize_str(self.as_str())),
+            deserialize_str,
+        ),
+        Some((serialize_compact, deserialize_compact)) => (
+            quote! {
+                if serializer.is_human_readable() {
+                    serializer.serialize_str(self.as_str())
+                } else {
+                    #serialize_compact
+                }
+            },
+            quote! {
+                if deserializer.is_human_readable() {
+                    #deserialize_str
+                } else {
+                    #deserialize_compact
+                }
+            },
+        ),
+    };
+
+    quote! {
+        impl #serde::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: #serde::Serializer,
+            {
+                #serialize_body
+            }
+        }
+
+        impl<'de> #serde::Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: #serde::Deserializer<'de>,
+            {
+                #deserialize_body
+            }
+        }
+    }
+}
+
+/// Generate `PartialReflect` trait implementation.
+#[cfg(feature = "reflect")]
+fn generate_partial_reflect_impl(
+    name: &Ident,
+    name_str: &str,
+    bevy_reflect: &Path,
+) -> TokenStream2 {
+    quote! {
+        impl #bevy_reflect::PartialReflect for #name {
+            fn get_represented_type_info(&self) -> Option<&'static #bevy_reflect::TypeInfo> {
+                Some(<Self as #bevy_reflect::Typed>::type_info())
+            }
+
+            fn into_partial_reflect(self: Box<Self>) -> Box<dyn #bevy_reflect::PartialReflect> {
+                self
+            }
+
+            fn as_partial_reflect(&self) -> &dyn #bevy_reflect::PartialReflect {
+                self
+            }
+
+            fn as_partial_reflect_mut(&mut self) -> &mut dyn #bevy_reflect::PartialReflect {
+                self
+            }
+
+            fn try_into_reflect(
+                self: Box<Self>,
+            ) -> Result<Box<dyn #bevy_reflect::Reflect>, Box<dyn #bevy_reflect::PartialReflect>>
+            {
+                Ok(self)
+            }
+
+            fn try_as_reflect(&self) -> Option<&dyn #bevy_reflect::Reflect> {
+                Some(self)
+            }
+
+            fn try_as_reflect_mut(&mut self) -> Option<&mut dyn #bevy_reflect::Reflect> {
+                Some(self)
+            }
+
+            fn apply(&mut self, value: &dyn #bevy_reflect::PartialReflect) {
+                if let Some(other) = value.try_downcast_ref::<Self>() {
+                    *self = *other;
+                }
+            }
+
+            fn try_apply(
+                &mut self,
+                value: &dyn #bevy_reflect::PartialReflect,
+            ) -> Result<(), #bevy_reflect::ApplyError> {
+                if let Some(other) = value.try_downcast_ref::<Self>() {
+                    *self = *other;
+                    Ok(())
+                } else {
+                    Err(#bevy_reflect::ApplyError::MismatchedTypes {
+                        from_type: value.reflect_type_path().to_string().into_boxed_str(),
+                        to_type: <Self as #bevy_reflect::TypePath>::type_path()
+                            .to_string()
+                            .into_boxed_str(),
+                    })
+                }
+            }
+
+            fn reflect_kind(&self) -> #bevy_reflect::ReflectKind {
+                #bevy_reflect::ReflectKind::Opaque
+            }
+
+            fn reflect_ref(&self) -> #bevy_reflect::ReflectRef<'_> {
+                #bevy_reflect::ReflectRef::Opaque(self)
+            }
+
+            fn reflect_mut(&mut self) -> #bevy_reflect::ReflectMut<'_> {
+                #bevy_reflect::ReflectMut::Opaque(self)
+            }
+
+            fn reflect_owned(self: Box<Self>) -> #bevy_reflect::ReflectOwned {
+                #bevy_reflect::ReflectOwned::Opaque(self)
+            }
+
+            fn reflect_hash(&self) -> Option<u64> {
+                Some(self.stable_hash())
+            }
+
+            fn reflect_partial_eq(
+                &self,
+                value: &dyn #bevy_reflect::PartialReflect,
+            ) -> Option<bool> {
+                value.try_downcast_ref::<Self>().map(|other| self == other)
+            }
+
+            fn debug(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}(\"{}\")", #name_str, self.as_str())
+            }
+
+            fn reflect_clone(&self) -> Result<Box<dyn #bevy_reflect::Reflect>, #bevy_reflect::ReflectCloneError> {
+                Ok(Box::new(*self))
+            }
+        }
+    }
+}
+
+/// Generate `Reflect` trait implementation.
+#[cfg(feature = "reflect")]
+fn generate_reflect_impl(name: &Ident, bevy_reflect: &Path) -> TokenStream2 {
+    quote! {
+        impl #bevy_reflect::Reflect for #name {
+            fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+                self
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+
+            fn into_reflect(self: Box<Self>) -> Box<dyn #bevy_reflect::Reflect> {
+                self
+            }
+
+            fn as_reflect(&self) -> &dyn #bevy_reflect::Reflect {
+                self
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn #bevy_reflect::Reflect {
+                self
+            }
+
+            fn set(
+                &mut self,
+                value: Box<dyn #bevy_reflect::Reflect>,
+            ) -> Result<(), Box<dyn #bevy_reflect::Reflect>> {
+                *self = *value.downcast()?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Generate `Typed`, `TypePath`, `FromReflect`, and `GetTypeRegistration` implementations.
+///
+/// The registration carries `ReflectDefault` only with `default_type_data`, i.e. when the
+/// derive generates `Default`. With `serde_type_data`, it also carries `ReflectSerialize` and
+/// `ReflectDeserialize`, which Bevy scenes need to save opaque types such as IDs.
+#[cfg(feature = "reflect")]
+fn generate_reflection_meta_impls(
+    name: &Ident,
+    name_str: &str,
+    bevy_reflect: &Path,
+    default_type_data: bool,
+    serde_type_data: bool,
+) -> TokenStream2 {
+    let default_type_data = default_type_data.then(|| {
+        quote! {
+            registration.insert::<#bevy_reflect::std_traits::ReflectDefault>(
+                #bevy_reflect::FromType::<Self>::from_type(),
+            );
+        }
+    });
+    let serde_type_data = serde_type_data.then(|| {
+        quote! {
+            registration.insert::<#bevy_reflect::ReflectSerialize>(
+                #bevy_reflect::FromType::<Self>::from_type(),
+            );
+            registration.insert::<#bevy_reflect::ReflectDeserialize>(
+                #bevy_reflect::FromType::<Self>::from_type(),
+            );
+        }
+    });
+
+    quote! {
+        impl #bevy_reflect::Typed for #name {
+            fn type_info() -> &'static #bevy_reflect::TypeInfo {
+                static CELL: #bevy_reflect::utility::NonGenericTypeInfoCell =
+                    #bevy_reflect::utility::NonGenericTypeInfoCell::new();
+                CELL.get_or_set(|| {
+                    #bevy_reflect::TypeInfo::Opaque(#bevy_reflect::OpaqueInfo::new::<Self>())
+                })
+            }
+        }
+
+        impl #bevy_reflect::TypePath for #name {
+            fn type_path() -> &'static str {
+                concat!(module_path!(), "::", #name_str)
+            }
+
+            fn short_type_path() -> &'static str {
+                #name_str
+            }
+        }
+
+        impl #bevy_reflect::FromReflect for #name {
+            fn from_reflect(reflect: &dyn #bevy_reflect::PartialReflect) -> Option<Self> {
+                reflect.try_downcast_ref::<Self>().copied()
+            }
+        }
+
+        impl #bevy_reflect::GetTypeRegistration for #name {
+            fn get_type_registration() -> #bevy_reflect::TypeRegistration {
+                let mut registration = #bevy_reflect::TypeRegistration::of::<Self>();
+                registration.insert::<#bevy_reflect::ReflectFromReflect>(
+                    #bevy_reflect::FromType::<Self>::from_type(),
+                );
+                registration.insert::<#bevy_reflect::ReflectFromPtr>(
+                    #bevy_reflect::FromType::<Self>::from_type(),
+                );
+                #default_type_data
+                #serde_type_data
+                registration
+            }
+        }
+    }
+}
+
+/// Generate inspector UI implementation for dev feature.
+#[cfg(all(feature = "dev", feature = "reflect"))]
+fn generate_inspector_impl(name: &Ident) -> TokenStream2 {
+    quote! {
+        impl bevy_inspector_egui::inspector_egui_impls::InspectorPrimitive for #name {
+            fn ui(
+                &mut self,
+                ui: &mut bevy_inspector_egui::egui::Ui,
+                _options: &dyn std::any::Any,
+                _id: bevy_inspector_egui::egui::Id,
+                _env: bevy_inspector_egui::reflect_inspector::InspectorUi<'_, '_>,
+            ) -> bool {
+                ui.label(self.as_str());
+                false // ID types are not editable
+            }
+
+            fn ui_readonly(
+                &self,
+                ui: &mut bevy_inspector_egui::egui::Ui,
+                _options: &dyn std::any::Any,
+                _id: bevy_inspector_egui::egui::Id,
+                _env: bevy_inspector_egui::reflect_inspector::InspectorUi<'_, '_>,
+            ) {
+                ui.label(self.as_str());
+            }
+        }
+    }
+}
+
+/// Derive macro for generating interned string ID types.
+///
+/// This macro generates a complete ID type with interner, methods, and trait implementations.
+///
+/// # Requirements
+///
+/// The struct must:
+/// - Be a newtype wrapping an `Interned<str>`: `bevy::ecs::intern::Interned<str>` or
+///   `msg_interned_id::intern::Interned<str>`
+/// - Manually derive: `Clone`, `Copy`, `PartialEq`, `Eq`, `Hash`, `Debug`
+///
+/// Any other shape (enums, unions, named fields, generic parameters, extra fields or a
+/// different inner type) is rejected with an error pointing at the offending item.
+///
+/// # Attributes
+///
+/// The derive accepts an optional `#[interned_id(...)]` helper attribute on the struct.
+/// Unknown keys are reported as errors at the key's span.
+///
+/// - `standalone` - Skip the Bevy reflection impls, the same as `skip(reflect)`
+/// - `crate = "path"` - Path to `msg_interned_id` (default: `::msg_interned_id`)
+/// - `validate = path::to::fn` - Reject strings before they are interned (see below)
+/// - `format = "snake_case"`, `ascii`, `no_leading_digit`, `min_len = N`, `max_len = N` -
+///   Built-in format rules, checked before `validate`. Formats: `snake_case`, `kebab-case`,
+///   `SCREAMING_SNAKE_CASE`, `camelCase`, `PascalCase`. Lengths are in bytes.
+/// - `namespaced` - Require `namespace:path` IDs and generate `namespace()` and `path()`.
+///   Format rules then apply to each part on its own; length limits to the whole ID.
+/// - `default_namespace = "name"` - Namespace given to bare paths (requires `namespaced`)
+/// - `hierarchical` or `hierarchical(separator = "/")` - IDs made of non-empty segments
+///   (default separator `.`), with `parent()`, `segments()`, `depth()`, `is_descendant_of()`
+///   and `child()`. Format rules apply to each segment.
+/// - `known(NAME = "value", ...)` - Well-known IDs as associated constants (`SpellId::NAME`),
+///   interned on first access, and listed in `SpellId::KNOWN`. Values the built-in rules
+///   reject are reported at their literal; a custom `validate` fn only runs on first access
+/// - `strict` - `TryFrom<&str>`/`TryFrom<String>` replace the `From` impls, and they,
+///   `FromStr` and `Deserialize` only look up existing IDs (see `get`) instead of interning.
+///   Deserialization errors list the closest existing IDs (see `suggest`).
+/// - `strict(serde)` - Only `Deserialize` looks up, so data files cannot invent IDs while
+///   code keeps the interning `From` and `FromStr` impls
+/// - `ord = "lexical"` or `ord = "intern_order"` - Generate `PartialOrd` and `Ord`, comparing
+///   by string content (deterministic across runs) or by `index()` (creation order, faster)
+/// - `normalize(...)` - Rewrite input before it is validated and interned: any of `trim`,
+///   `lowercase` and `nfc` (Unicode Normalization Form C), applied in that order
+/// - `skip(...)` - Leave out generated impls so you can write your own: any of `Display`,
+///   `From`, `FromStr`, `Deref`, `Default`, `serde` and `reflect`. `Default` is the empty
+///   string, so `namespaced`, `hierarchical` and a non-zero `min_len` require `skip(Default)`
+/// - `bevy_reflect = "path"` - Path used for reflection (default: `bevy::reflect`)
+/// - `bevy_ecs = "path"` - Deprecated and ignored, with a warning; IDs no longer intern
+///   through `bevy_ecs`
+/// - `serde = "path"` - Path used for serialization (default: `serde`)
+/// - `serde_repr = "stable_hash"` or `serde_repr = "index"` - Binary serde formats store the
+///   ID's `stable_hash()` (`u64`) or `index()` (`u32`) instead of its string; human-readable
+///   formats keep the string. `serde_repr = "string"` is the default.
+///
+/// The defaults assume a `bevy` crate (or module) is in scope. Crates that depend on the
+/// Bevy sub-crates directly, or re-export Bevy under another name, can point the derive at
+/// them instead:
+///
+/// ```rust,ignore
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(bevy_reflect = "bevy_reflect", serde = "::serde")]
+/// pub struct SpellId(bevy_ecs::intern::Interned<str>);
+/// ```
+///
+/// # Generated Code
+///
+/// The macro generates:
+/// 1. A static interner unique to this type
+/// 2. Core methods: `new()` and `as_str()`
+/// 3. Standard traits: Display, From, Deref, Default
+/// 4. Serialization: Serialize, Deserialize (`serde` feature)
+/// 5. Bevy reflection: Full reflection hierarchy (`reflect` feature)
+/// 6. Inspector UI (`dev` feature only)
+///
+/// # Examples
+///
+/// ## Basic Usage
+///
+/// ```rust,ignore
+/// use msg_interned_id::InternedId;
+/// use bevy::prelude::*;
+///
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// pub struct SpellId(bevy::ecs::intern::Interned<str>);
+///
+/// let id = SpellId::new("fireball");
+/// assert_eq!(id.as_str(), "fireball");
+/// assert_eq!(&*id, "fireball"); // Deref to &str
+/// ```
+///
+/// ## As ECS Component
+///
+/// ```rust,ignore
+/// use msg_interned_id::InternedId;
+/// use bevy::prelude::*;
+///
+/// #[derive(Component, InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// pub struct ItemId(bevy::ecs::intern::Interned<str>);
+///
+/// fn spawn_item(mut commands: Commands) {
+///     commands.spawn(ItemId::new("health_potion"));
+/// }
+/// ```
+///
+/// ## With Serialization
+///
+/// ```rust,ignore
+/// use msg_interned_id::InternedId;
+/// use bevy::prelude::*;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// pub struct QuestId(bevy::ecs::intern::Interned<str>);
+///
+/// // Serializes as: "main_quest"
+/// // Deserializes from: "main_quest"
+/// ```
+///
+/// ## With Validation
+///
+/// ```rust,ignore
+/// use msg_interned_id::InternedId;
+///
+/// fn non_empty(id: &str) -> Result<(), &'static str> {
+///     if id.is_empty() { Err("must not be empty") } else { Ok(()) }
+/// }
+///
+/// // Generates `try_new`; `new`, `From` and `Default` panic on rejected input,
+/// // `FromStr` and `Deserialize` return errors.
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(validate = non_empty, skip(Default))]
+/// pub struct ModId(bevy::ecs::intern::Interned<str>);
+///
+/// assert!(ModId::try_new("").is_err());
+/// ```
+///
+/// ## Without Bevy
+///
+/// ```rust,ignore
+/// use msg_interned_id::InternedId;
+///
+/// // No Bevy dependency: interned by `msg_interned_id`, no reflection impls.
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(standalone)]
+/// pub struct SpellId(msg_interned_id::intern::Interned<str>);
+/// ```
+#[proc_macro_derive(InternedId, attributes(interned_id))]
+pub fn derive_interned_id(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate a use of a deprecated constant at the `bevy_ecs` key, so the compiler warns
+/// about the key without failing the build.
+fn generate_bevy_ecs_deprecation(span: proc_macro2::Span) -> TokenStream2 {
+    let bevy_ecs = Ident::new("bevy_ecs", span);
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = "`interned_id(bevy_ecs = \"...\")` is ignored: every ID interns \
+                through `msg_interned_id`'s own interner; remove it")]
+            #[allow(non_upper_case_globals)]
+            const #bevy_ecs: () = ();
+            #bevy_ecs
+        };
+    }
+}
+
+/// Validate the input and generate every implementation for the ID type.
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    // Report shape and attribute problems together rather than one per compile.
+    let (field_ctor, options) = match (
+        attr::validate_shape(input),
+        Options::from_attrs(&input.attrs),
+    ) {
+        (Ok(field_ctor), Ok(options)) => (field_ctor, options),
+        (Err(mut shape_err), Err(attr_err)) => {
+            shape_err.combine(attr_err);
+            return Err(shape_err);
+        }
+        (Err(err), Ok(_)) | (Ok(_), Err(err)) => return Err(err),
+    };
+
+    let name = &input.ident;
+    let interner_name = format_ident!("{}_INTERNER", name.to_string().to_uppercase());
+    let name_str = name.to_string();
+    rules::check_known(&name_str, &options)?;
+
+    // Generate each section using helper functions, leaving out anything the user skipped
+    let skip = &options.skip;
+    let core = generate_core_impl(name, &name_str, &interner_name, &field_ctor, &options);
+    let trait_impl = generate_trait_impl(name, &name_str, &options);
+    let display = (!skip.display).then(|| generate_display_impl(name));
+    let ord = options
+        .ord
+        .as_ref()
+        .map(|ordering| generate_ord_impl(name, ordering));
+    let from = (!skip.from).then(|| generate_from_impls(name, &name_str, &options));
+    let from_str = (!skip.from_str).then(|| generate_from_str_impl(name, &name_str, &options));
+    let deref = (!skip.deref).then(|| generate_deref_impl(name));
+    let default = (!skip.default).then(|| generate_default_impl(name));
+    #[cfg(feature = "serde")]
+    let serde = (!skip.serde).then(|| generate_serde_impls(name, &name_str, &options));
+    #[cfg(not(feature = "serde"))]
+    let serde = quote! {};
+
+    // Standalone IDs must not mention Bevy at all.
+    #[cfg(feature = "reflect")]
+    let reflection = (!options.standalone && !skip.reflect).then(|| {
+        let partial_reflect = generate_partial_reflect_impl(name, &name_str, &options.bevy_reflect);
+        let reflect = generate_reflect_impl(name, &options.bevy_reflect);
+        let reflection_meta = generate_reflection_meta_impls(
+            name,
+            &name_str,
+            &options.bevy_reflect,
+            !skip.default,
+            cfg!(feature = "serde") && !skip.serde,
+        );
+        #[cfg(feature = "dev")]
+        let inspector = generate_inspector_impl(name);
+        #[cfg(not(feature = "dev"))]
+        let inspector = quote! {};
+
+        quote! {
+            #partial_reflect
+            #reflect
+            #reflection_meta
+            #inspector
+        }
+    });
+    #[cfg(not(feature = "reflect"))]
+    let reflection = quote! {};
+    let deprecations = options.bevy_ecs.map(generate_bevy_ecs_deprecation);
+
+    Ok(quote! {
+        #deprecations
+        #core
+        #trait_impl
+        #display
+        #ord
+        #from
+        #from_str
+        #deref
+        #default
+        #serde
+        #reflection
+    })
+}
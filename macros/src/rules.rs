@@ -0,0 +1,301 @@
+//! Expansion-time checks of `known(...)` values against the built-in rules.
+//!
+//! The generated `try_new` normalizes, resolves the namespace, checks the hierarchy and the
+//! format rules, then runs any custom `validate` function. Everything but the last step only
+//! depends on the attribute, so a declared value the type would reject is reported at its
+//! literal instead of panicking on first access. The checks mirror `msg_interned_id`'s
+//! `normalize`, `namespace`, `hierarchy` and `format` modules, which this crate cannot depend
+//! on, and report the same messages; the tests below run both on the same inputs.
+
+use syn::LitStr;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::attr::{FormatRules, Options, push_error};
+
+/// Separator of namespaced IDs, as `msg_interned_id::namespace::SEPARATOR`.
+const NAMESPACE_SEPARATOR: char = ':';
+
+/// Check every `known(...)` value of `type_name`, reporting each rejected one at its literal.
+pub(crate) fn check_known(type_name: &str, options: &Options) -> syn::Result<()> {
+    let mut errors = None;
+    for (_, lit) in &options.known {
+        if let Err(message) = check_value(type_name, &lit.value(), options) {
+            push_error(&mut errors, syn::Error::new(lit.span(), message));
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+/// Check `value` as the generated `try_new` of `type_name` would, up to its custom
+/// `validate` function, with the message of the `IdError` it would return.
+fn check_value(type_name: &str, value: &str, options: &Options) -> Result<(), String> {
+    check(value, options)
+        .map_err(|(value, reason)| format!("invalid {type_name} {value:?}: {reason}"))
+}
+
+/// Run the built-in steps of the generated `prepare` on `value`, returning the value as it was
+/// when rejected together with the reason.
+fn check(value: &str, options: &Options) -> Result<(), (String, String)> {
+    let steps = &options.normalize;
+    let mut id = if steps.trim {
+        value.trim().to_owned()
+    } else {
+        value.to_owned()
+    };
+    if steps.lowercase {
+        id = id.to_lowercase();
+    }
+    if steps.nfc {
+        id = id.nfc().collect();
+    }
+
+    if options.namespaced {
+        let default = options.default_namespace.as_ref().map(LitStr::value);
+        id = resolve_namespace(&id, default.as_deref()).map_err(|reason| (id, reason))?;
+    }
+    if let Some(separator) = &options.hierarchical {
+        check_hierarchy(&id, separator.value()).map_err(|reason| (id.clone(), reason))?;
+    }
+
+    let separator = if options.namespaced {
+        Some(NAMESPACE_SEPARATOR)
+    } else {
+        options.hierarchical.as_ref().map(syn::LitChar::value)
+    };
+    check_format(&id, separator, options).map_err(|reason| (id, reason))
+}
+
+/// As `msg_interned_id::namespace::resolve`.
+fn resolve_namespace(id: &str, default: Option<&str>) -> Result<String, String> {
+    let Some((namespace, path)) = id.split_once(NAMESPACE_SEPARATOR) else {
+        let Some(default) = default else {
+            return Err(format!("expected `namespace{NAMESPACE_SEPARATOR}path`"));
+        };
+        if id.is_empty() {
+            return Err("path must not be empty".to_owned());
+        }
+        return Ok(format!("{default}{NAMESPACE_SEPARATOR}{id}"));
+    };
+    if namespace.is_empty() {
+        return Err("namespace must not be empty".to_owned());
+    }
+    if path.is_empty() {
+        return Err("path must not be empty".to_owned());
+    }
+    if let Some(pos) = path.find(NAMESPACE_SEPARATOR) {
+        return Err(format!(
+            "unexpected second `{NAMESPACE_SEPARATOR}` (at byte {})",
+            namespace.len() + 1 + pos
+        ));
+    }
+    Ok(id.to_owned())
+}
+
+/// As `msg_interned_id::hierarchy::check`.
+fn check_hierarchy(id: &str, separator: char) -> Result<(), String> {
+    let mut offset = 0;
+    for segment in id.split(separator) {
+        if segment.is_empty() {
+            return Err(format!("segments must not be empty (at byte {offset})"));
+        }
+        offset += segment.len() + separator.len_utf8();
+    }
+    Ok(())
+}
+
+/// As `msg_interned_id::format::IdFormat::check`, or `check_segments` with a separator.
+fn check_format(id: &str, separator: Option<char>, options: &Options) -> Result<(), String> {
+    let rules = &options.format;
+    let len = |lit: &Option<syn::LitInt>| {
+        lit.as_ref()
+            .and_then(|lit| lit.base10_parse::<usize>().ok())
+    };
+    if let Some(max) = len(&rules.max_len)
+        && id.len() > max
+    {
+        return Err(format!("must be at most {max} bytes long (at byte {max})"));
+    }
+    if let Some(min) = len(&rules.min_len)
+        && id.len() < min
+    {
+        return Err(format!(
+            "must be at least {min} bytes long (at byte {})",
+            id.len()
+        ));
+    }
+
+    let segments: Vec<&str> = match separator {
+        Some(separator) => id.split(separator).collect(),
+        None => vec![id],
+    };
+    let mut offset = 0;
+    for segment in segments {
+        check_chars(segment, offset, options)?;
+        offset += segment.len() + separator.map_or(0, char::len_utf8);
+    }
+    Ok(())
+}
+
+/// The character and case rules, with positions shifted by `offset`.
+fn check_chars(value: &str, offset: usize, options: &Options) -> Result<(), String> {
+    let rules = &options.format;
+    if rules.ascii
+        && let Some(pos) = value.bytes().position(|byte| !byte.is_ascii())
+    {
+        return Err(format!("must be ASCII (at byte {})", offset + pos));
+    }
+    if rules.no_leading_digit && value.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!("must not start with a digit (at byte {offset})"));
+    }
+    if let Some(style) = &rules.case
+        && let Some(pos) = case_violation(&style.to_string(), value)
+    {
+        let name = FormatRules::case_name(style);
+        return Err(format!("must be {name} (at byte {})", offset + pos));
+    }
+    Ok(())
+}
+
+/// As `msg_interned_id::format::CaseStyle::violation`, for the named `CaseStyle` variant.
+fn case_violation(variant: &str, value: &str) -> Option<usize> {
+    let bytes = value.as_bytes();
+    let separator = match variant {
+        "Snake" | "ScreamingSnake" => Some(b'_'),
+        "Kebab" => Some(b'-'),
+        _ => None,
+    };
+    for (pos, &byte) in bytes.iter().enumerate() {
+        let allowed = if pos == 0 {
+            match variant {
+                "ScreamingSnake" | "Pascal" => byte.is_ascii_uppercase(),
+                _ => byte.is_ascii_lowercase(),
+            }
+        } else if Some(byte) == separator {
+            bytes[pos - 1] != byte && pos + 1 < bytes.len()
+        } else {
+            match variant {
+                "Snake" | "Kebab" => byte.is_ascii_lowercase() || byte.is_ascii_digit(),
+                "ScreamingSnake" => byte.is_ascii_uppercase() || byte.is_ascii_digit(),
+                _ => byte.is_ascii_alphanumeric(),
+            }
+        };
+        if !allowed {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+// Each case derives a real ID type with the given attribute and compares its `try_new` with
+// `check_value` on every input, error messages included.
+#[cfg(test)]
+mod tests {
+    use super::check_value;
+    use crate::attr::Options;
+
+    macro_rules! assert_agree {
+        ($name:ident($($attr:tt)*), [$($input:expr),* $(,)?]) => {{
+            #[derive(msg_interned_id::InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+            #[interned_id(standalone, skip(Default, serde), $($attr)*)]
+            struct $name(msg_interned_id::intern::Interned<str>);
+
+            let attrs = [syn::parse_quote! {
+                #[interned_id(standalone, skip(Default, serde), $($attr)*)]
+            }];
+            let options = Options::from_attrs(&attrs).unwrap_or_else(|err| panic!("{err}"));
+            for input in [$($input),*] {
+                let runtime = $name::try_new(input).map(|_| ()).map_err(|err| err.to_string());
+                let expansion = check_value(stringify!($name), input, &options);
+                assert_eq!(expansion, runtime, "{} disagrees on {input:?}", stringify!($name));
+            }
+        }};
+    }
+
+    #[test]
+    fn test_format_rules_agree() {
+        assert_agree!(
+            SnakeId(format = "snake_case", min_len = 2, max_len = 8),
+            [
+                "fire",
+                "f",
+                "fire_ball",
+                "fire__ball",
+                "_fire",
+                "fire_",
+                "Fire",
+                "fire9",
+                "9fire",
+            ]
+        );
+        assert_agree!(
+            KebabId(format = "kebab-case"),
+            ["fire-ball", "fire--ball", "fire_ball", ""]
+        );
+        assert_agree!(
+            ScreamingId(format = "SCREAMING_SNAKE_CASE"),
+            ["FIRE_BALL", "Fire", "F_1"]
+        );
+        assert_agree!(
+            CamelId(format = "camelCase"),
+            ["fireBall", "FireBall", "fire_ball", "f1"]
+        );
+        assert_agree!(
+            PascalId(format = "PascalCase"),
+            ["FireBall", "fireBall", "Fire-Ball"]
+        );
+        assert_agree!(
+            LooseId(ascii, no_leading_digit),
+            ["caf\u{e9}", "9lives", "lives9", ""]
+        );
+    }
+
+    #[test]
+    fn test_normalized_rules_agree() {
+        assert_agree!(
+            TagId(normalize(trim, lowercase, nfc), format = "snake_case"),
+            ["  Fire_Ball ", "CAFE\u{301}", " fire ball ", "\t",]
+        );
+        assert_agree!(
+            ShortId(normalize(trim), max_len = 4),
+            ["  fire  ", " fires "]
+        );
+    }
+
+    #[test]
+    fn test_namespace_rules_agree() {
+        assert_agree!(
+            AssetId(namespaced, format = "snake_case"),
+            [
+                "core:fireball",
+                "fireball",
+                ":fireball",
+                "core:",
+                "core:fire:ball",
+                "Core:fire",
+            ]
+        );
+        assert_agree!(
+            ItemId(namespaced, default_namespace = "core", max_len = 12),
+            ["sword", "", "mod:sword", "core:longsword", "longsword",]
+        );
+    }
+
+    #[test]
+    fn test_hierarchy_rules_agree() {
+        assert_agree!(
+            EffectId(hierarchical, format = "snake_case"),
+            [
+                "buff.speed",
+                "buff..speed",
+                ".buff",
+                "buff.",
+                "buff.Speed",
+                "buff.speed_up",
+            ]
+        );
+        assert_agree!(
+            ConfigKey(hierarchical(separator = "/"), ascii),
+            ["audio/volume", "audio//volume", "audio/volum\u{e9}",]
+        );
+    }
+}
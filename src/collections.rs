@@ -0,0 +1,612 @@
+//! Dense collections keyed by interned IDs.
+//!
+//! Every ID type numbers its IDs densely (see [`InternedId::index`]), so per-ID data can be
+//! stored in a `Vec` or a bitset instead of a hash map. Both collections iterate in index
+//! order, which is the order the IDs were created in, and serialize their keys as strings so
+//! saved data does not depend on that order.
+//!
+//! For sparse data, [`IdHashMap`] and [`IdHashSet`] are the standard hash collections with
+//! [`IdBuildHasher`], which hashes an ID's address instead of running SipHash over it.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::iter::Enumerate;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use crate::InternedId;
+use crate::hash::IdBuildHasher;
+
+/// A `HashMap` keyed by IDs, hashing them with [`IdBuildHasher`].
+///
+/// Create one with `IdHashMap::default()`; `HashMap::new` only exists for the default hasher.
+///
+/// ```
+/// use msg_interned_id::InternedId;
+/// use msg_interned_id::collections::IdHashMap;
+///
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(standalone)]
+/// pub struct EnemyId(msg_interned_id::intern::Interned<str>);
+///
+/// let mut hp: IdHashMap<EnemyId, u32> = IdHashMap::default();
+/// hp.insert(EnemyId::new("goblin"), 50);
+/// assert_eq!(hp.get(&EnemyId::new("goblin")), Some(&50));
+/// ```
+pub type IdHashMap<K, V> = HashMap<K, V, IdBuildHasher>;
+
+/// A `HashSet` of IDs, hashing them with [`IdBuildHasher`].
+pub type IdHashSet<K> = HashSet<K, IdBuildHasher>;
+
+/// The ID at `index`, which must belong to an ID that has been created.
+fn id_at<Id: InternedId>(index: usize) -> Id {
+    u32::try_from(index)
+        .ok()
+        .and_then(Id::from_index)
+        .expect("collection index belongs to a created ID")
+}
+
+/// A map from IDs to values, stored as a `Vec` indexed by [`InternedId::index`].
+///
+/// Lookups are a bounds check and an index, with no hashing. Memory grows with the highest
+/// index stored, so it suits ID types where most IDs have a value, such as per-spell
+/// cooldowns.
+///
+/// ```
+/// use msg_interned_id::InternedId;
+/// use msg_interned_id::collections::IdVec;
+///
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(standalone)]
+/// pub struct SpellId(msg_interned_id::intern::Interned<str>);
+///
+/// let fireball = SpellId::new("fireball");
+/// let mut cooldowns = IdVec::new();
+/// cooldowns.insert(fireball, 2.5);
+/// assert_eq!(cooldowns[fireball], 2.5);
+/// assert_eq!(cooldowns.get(SpellId::new("ice_bolt")), None);
+/// ```
+pub struct IdVec<Id, V> {
+    values: Vec<Option<V>>,
+    len: usize,
+    marker: PhantomData<Id>,
+}
+
+impl<Id, V> IdVec<Id, V> {
+    /// Create an empty map.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of IDs with a value.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no ID has a value.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove every value.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.len = 0;
+    }
+}
+
+impl<Id: InternedId, V> IdVec<Id, V> {
+    /// Set the value for `id`, returning the previous one.
+    pub fn insert(&mut self, id: Id, value: V) -> Option<V> {
+        let slot = self.slot_mut(id);
+        let previous = slot.replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    /// Remove and return the value for `id`.
+    pub fn remove(&mut self, id: Id) -> Option<V> {
+        let previous = self.values.get_mut(id.index() as usize)?.take();
+        if previous.is_some() {
+            self.len -= 1;
+        }
+        previous
+    }
+
+    /// The value for `id`.
+    #[must_use]
+    pub fn get(&self, id: Id) -> Option<&V> {
+        self.values.get(id.index() as usize)?.as_ref()
+    }
+
+    /// The value for `id`, mutably.
+    #[must_use]
+    pub fn get_mut(&mut self, id: Id) -> Option<&mut V> {
+        self.values.get_mut(id.index() as usize)?.as_mut()
+    }
+
+    /// The value for `id`, inserting the result of `default` if there is none.
+    pub fn get_or_insert_with(&mut self, id: Id, default: impl FnOnce() -> V) -> &mut V {
+        if !self.contains_key(id) {
+            self.len += 1;
+        }
+        self.slot_mut(id).get_or_insert_with(default)
+    }
+
+    /// Whether `id` has a value.
+    #[must_use]
+    pub fn contains_key(&self, id: Id) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Keep only the entries for which `keep` returns `true`.
+    pub fn retain(&mut self, mut keep: impl FnMut(Id, &mut V) -> bool) {
+        for (index, slot) in self.values.iter_mut().enumerate() {
+            if let Some(value) = slot
+                && !keep(id_at(index), value)
+            {
+                *slot = None;
+                self.len -= 1;
+            }
+        }
+    }
+
+    /// Entries in index order.
+    pub fn iter(&self) -> Iter<'_, Id, V> {
+        Iter {
+            inner: self.values.iter().enumerate(),
+            remaining: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Entries in index order, with mutable values.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Id, &mut V)> {
+        self.values
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| Some((id_at(index), slot.as_mut()?)))
+    }
+
+    /// IDs with a value, in index order.
+    pub fn keys(&self) -> impl Iterator<Item = Id> + '_ {
+        self.iter().map(|(id, _)| id)
+    }
+
+    /// Values in index order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.values.iter().flatten()
+    }
+
+    /// Values in index order, mutably.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.values.iter_mut().flatten()
+    }
+
+    fn slot_mut(&mut self, id: Id) -> &mut Option<V> {
+        let index = id.index() as usize;
+        if index >= self.values.len() {
+            self.values.resize_with(index + 1, || None);
+        }
+        &mut self.values[index]
+    }
+}
+
+impl<Id, V> Default for IdVec<Id, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id, V: Clone> Clone for IdVec<Id, V> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Id: InternedId, V: PartialEq> PartialEq for IdVec<Id, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|((a, a_value), (b, b_value))| a.index() == b.index() && a_value == b_value)
+    }
+}
+
+impl<Id: InternedId, V: Eq> Eq for IdVec<Id, V> {}
+
+impl<Id: InternedId, V: fmt::Debug> fmt::Debug for IdVec<Id, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(id, value)| (id.as_str(), value)))
+            .finish()
+    }
+}
+
+impl<Id: InternedId, V> Index<Id> for IdVec<Id, V> {
+    type Output = V;
+
+    /// # Panics
+    ///
+    /// Panics if `id` has no value.
+    fn index(&self, id: Id) -> &V {
+        self.get(id)
+            .unwrap_or_else(|| panic!("no value for {} {:?}", Id::TYPE_NAME, id.as_str()))
+    }
+}
+
+impl<Id: InternedId, V> IndexMut<Id> for IdVec<Id, V> {
+    /// # Panics
+    ///
+    /// Panics if `id` has no value.
+    fn index_mut(&mut self, id: Id) -> &mut V {
+        self.get_mut(id)
+            .unwrap_or_else(|| panic!("no value for {} {:?}", Id::TYPE_NAME, id.as_str()))
+    }
+}
+
+impl<Id: InternedId, V> FromIterator<(Id, V)> for IdVec<Id, V> {
+    fn from_iter<I: IntoIterator<Item = (Id, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<Id: InternedId, V> Extend<(Id, V)> for IdVec<Id, V> {
+    fn extend<I: IntoIterator<Item = (Id, V)>>(&mut self, iter: I) {
+        for (id, value) in iter {
+            self.insert(id, value);
+        }
+    }
+}
+
+impl<'a, Id: InternedId, V> IntoIterator for &'a IdVec<Id, V> {
+    type Item = (Id, &'a V);
+    type IntoIter = Iter<'a, Id, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of an [`IdVec`], in index order.
+pub struct Iter<'a, Id, V> {
+    inner: Enumerate<slice::Iter<'a, Option<V>>>,
+    remaining: usize,
+    marker: PhantomData<Id>,
+}
+
+impl<'a, Id: InternedId, V> Iterator for Iter<'a, Id, V> {
+    type Item = (Id, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (id, value) = self
+            .inner
+            .find_map(|(index, slot)| Some((id_at(index), slot.as_ref()?)))?;
+        self.remaining -= 1;
+        Some((id, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<Id: InternedId, V> ExactSizeIterator for Iter<'_, Id, V> {}
+
+/// A set of IDs, stored as one bit per [`InternedId::index`].
+///
+/// ```
+/// use msg_interned_id::InternedId;
+/// use msg_interned_id::collections::IdBitSet;
+///
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(standalone)]
+/// pub struct RecipeId(msg_interned_id::intern::Interned<str>);
+///
+/// let mut known = IdBitSet::new();
+/// known.insert(RecipeId::new("bread"));
+/// assert!(known.contains(RecipeId::new("bread")));
+/// assert!(!known.contains(RecipeId::new("cake")));
+/// ```
+pub struct IdBitSet<Id> {
+    words: Vec<u64>,
+    len: usize,
+    marker: PhantomData<Id>,
+}
+
+impl<Id> IdBitSet<Id> {
+    /// Create an empty set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of IDs in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove every ID.
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+    }
+
+    /// Word and bit mask of `index`.
+    fn locate(index: u32) -> (usize, u64) {
+        ((index / u64::BITS) as usize, 1 << (index % u64::BITS))
+    }
+}
+
+impl<Id: InternedId> IdBitSet<Id> {
+    /// Add `id`, returning whether it was newly added.
+    pub fn insert(&mut self, id: Id) -> bool {
+        let (word, mask) = Self::locate(id.index());
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let added = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        if added {
+            self.len += 1;
+        }
+        added
+    }
+
+    /// Remove `id`, returning whether it was present.
+    pub fn remove(&mut self, id: Id) -> bool {
+        let (word, mask) = Self::locate(id.index());
+        let Some(bits) = self.words.get_mut(word) else {
+            return false;
+        };
+        let removed = *bits & mask != 0;
+        *bits &= !mask;
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Whether `id` is in the set.
+    #[must_use]
+    pub fn contains(&self, id: Id) -> bool {
+        let (word, mask) = Self::locate(id.index());
+        self.words.get(word).is_some_and(|bits| bits & mask != 0)
+    }
+
+    /// IDs in index order.
+    pub fn iter(&self) -> BitIter<'_, Id> {
+        BitIter {
+            words: self.words.iter().enumerate(),
+            base: 0,
+            current: 0,
+            remaining: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Id> Default for IdBitSet<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id> Clone for IdBitSet<Id> {
+    fn clone(&self) -> Self {
+        Self {
+            words: self.words.clone(),
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Id: InternedId> PartialEq for IdBitSet<Id> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .map(|id| id.index())
+                .eq(other.iter().map(|id| id.index()))
+    }
+}
+
+impl<Id: InternedId> Eq for IdBitSet<Id> {}
+
+impl<Id: InternedId> fmt::Debug for IdBitSet<Id> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|id| id.as_str()))
+            .finish()
+    }
+}
+
+impl<Id: InternedId> FromIterator<Id> for IdBitSet<Id> {
+    fn from_iter<I: IntoIterator<Item = Id>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<Id: InternedId> Extend<Id> for IdBitSet<Id> {
+    fn extend<I: IntoIterator<Item = Id>>(&mut self, iter: I) {
+        for id in iter {
+            self.insert(id);
+        }
+    }
+}
+
+impl<'a, Id: InternedId> IntoIterator for &'a IdBitSet<Id> {
+    type Item = Id;
+    type IntoIter = BitIter<'a, Id>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the IDs in an [`IdBitSet`], in index order.
+pub struct BitIter<'a, Id> {
+    words: Enumerate<slice::Iter<'a, u64>>,
+    /// Index of the lowest bit of `current`.
+    base: usize,
+    /// Bits of the current word not yet yielded.
+    current: u64,
+    remaining: usize,
+    marker: PhantomData<Id>,
+}
+
+impl<Id: InternedId> Iterator for BitIter<'_, Id> {
+    type Item = Id;
+
+    fn next(&mut self) -> Option<Id> {
+        while self.current == 0 {
+            let (word, &bits) = self.words.next()?;
+            self.base = word * u64::BITS as usize;
+            self.current = bits;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        self.remaining -= 1;
+        Some(id_at(self.base + bit))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<Id: InternedId> ExactSizeIterator for BitIter<'_, Id> {}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::de::{self, MapAccess, SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{IdBitSet, IdVec};
+    use crate::InternedId;
+
+    /// Serialized as a map from ID strings to values.
+    impl<Id: InternedId, V: Serialize> Serialize for IdVec<Id, V> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.iter().map(|(id, value)| (id.as_str(), value)))
+        }
+    }
+
+    impl<'de, Id: InternedId, V: Deserialize<'de>> Deserialize<'de> for IdVec<Id, V> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct MapVisitor<Id, V>(PhantomData<(Id, V)>);
+
+            impl<'de, Id: InternedId, V: Deserialize<'de>> Visitor<'de> for MapVisitor<Id, V> {
+                type Value = IdVec<Id, V>;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a map keyed by {} strings", Id::TYPE_NAME)
+                }
+
+                fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                    let mut values = IdVec::new();
+                    while let Some((key, value)) = map.next_entry::<String, V>()? {
+                        let id = Id::parse(&key).map_err(de::Error::custom)?;
+                        values.insert(id, value);
+                    }
+                    Ok(values)
+                }
+            }
+
+            deserializer.deserialize_map(MapVisitor(PhantomData))
+        }
+    }
+
+    /// Serialized as a sequence of ID strings.
+    impl<Id: InternedId> Serialize for IdBitSet<Id> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter().map(|id| id.as_str()))
+        }
+    }
+
+    impl<'de, Id: InternedId> Deserialize<'de> for IdBitSet<Id> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct SeqVisitor<Id>(PhantomData<Id>);
+
+            impl<'de, Id: InternedId> Visitor<'de> for SeqVisitor<Id> {
+                type Value = IdBitSet<Id>;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a sequence of {} strings", Id::TYPE_NAME)
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut set = IdBitSet::new();
+                    while let Some(key) = seq.next_element::<String>()? {
+                        set.insert(Id::parse(&key).map_err(de::Error::custom)?);
+                    }
+                    Ok(set)
+                }
+            }
+
+            deserializer.deserialize_seq(SeqVisitor(PhantomData))
+        }
+    }
+}
+
+// Both collections reflect as opaque values: their layout follows interning order, which
+// differs between runs, so it must not be read or applied field by field. With the `serde`
+// feature their registrations carry `ReflectSerialize` and `ReflectDeserialize`, so
+// reflection-based serialization (scenes, saves) goes through the string-keyed impls above.
+for_each_bevy_version! {
+    use bevy_reflect::impl_reflect_opaque;
+    use bevy_reflect::std_traits::ReflectDefault;
+    #[cfg(feature = "serde")]
+    use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+    #[cfg(not(feature = "serde"))]
+    impl_reflect_opaque!((in msg_interned_id::collections) super::IdVec<Id, V: Clone>(
+        Clone, Default
+    ));
+    #[cfg(feature = "serde")]
+    impl_reflect_opaque!((in msg_interned_id::collections) super::IdVec<
+        Id: crate::InternedId,
+        V: Clone + serde::Serialize + serde::de::DeserializeOwned
+    >(Clone, Default, Serialize, Deserialize));
+
+    #[cfg(not(feature = "serde"))]
+    impl_reflect_opaque!((in msg_interned_id::collections) super::IdBitSet<Id>(Clone, Default));
+    #[cfg(feature = "serde")]
+    impl_reflect_opaque!((in msg_interned_id::collections) super::IdBitSet<Id: crate::InternedId>(
+        Clone, Default, Serialize, Deserialize
+    ));
+}
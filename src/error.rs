@@ -0,0 +1,164 @@
+//! Errors produced when a string cannot be turned into an ID.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::format::FormatError;
+use crate::hierarchy::HierarchyError;
+use crate::namespace::NamespaceError;
+
+/// Error returned by the generated `try_new` and `FromStr` impls, and reported by
+/// `Deserialize`, when a string is rejected.
+#[derive(Debug)]
+pub struct IdError {
+    type_name: &'static str,
+    value: Box<str>,
+    kind: IdErrorKind,
+    suggestions: Vec<&'static str>,
+}
+
+/// Why an [`IdError`] was raised.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum IdErrorKind {
+    /// The value broke one of the built-in format rules.
+    Format(FormatError),
+    /// The value is not a valid `namespace:path` ID.
+    Namespace(NamespaceError),
+    /// The value is not a valid hierarchical ID.
+    Hierarchy(HierarchyError),
+    /// The user-supplied `validate` function rejected the value.
+    Rejected(Box<dyn Error + Send + Sync>),
+    /// A lookup-only conversion found no existing ID with this value.
+    Unknown,
+}
+
+impl IdError {
+    /// Report a violated format rule.
+    #[must_use]
+    pub fn format(type_name: &'static str, value: &str, error: FormatError) -> Self {
+        Self {
+            type_name,
+            value: value.into(),
+            kind: IdErrorKind::Format(error),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Report a malformed `namespace:path` ID.
+    #[must_use]
+    pub fn namespace(type_name: &'static str, value: &str, error: NamespaceError) -> Self {
+        Self {
+            type_name,
+            value: value.into(),
+            kind: IdErrorKind::Namespace(error),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Report a malformed hierarchical ID.
+    #[must_use]
+    pub fn hierarchy(type_name: &'static str, value: &str, error: HierarchyError) -> Self {
+        Self {
+            type_name,
+            value: value.into(),
+            kind: IdErrorKind::Hierarchy(error),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Wrap an error returned by a user-supplied validator.
+    pub fn rejected(
+        type_name: &'static str,
+        value: &str,
+        reason: impl Into<Box<dyn Error + Send + Sync>>,
+    ) -> Self {
+        Self {
+            type_name,
+            value: value.into(),
+            kind: IdErrorKind::Rejected(reason.into()),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Report a value that a lookup-only conversion could not find.
+    #[must_use]
+    pub fn unknown(type_name: &'static str, value: &str) -> Self {
+        Self {
+            type_name,
+            value: value.into(),
+            kind: IdErrorKind::Unknown,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Attach existing IDs the rejected value may have meant, nearest first. They are listed
+    /// in the message as "did you mean ...".
+    #[must_use]
+    pub fn with_suggestions(mut self, suggestions: impl IntoIterator<Item = &'static str>) -> Self {
+        self.suggestions.extend(suggestions);
+        self
+    }
+
+    /// Name of the ID type that rejected the value, e.g. `"SpellId"`.
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The rejected input.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Why the value was rejected.
+    #[must_use]
+    pub fn kind(&self) -> &IdErrorKind {
+        &self.kind
+    }
+
+    /// Existing IDs the rejected value may have meant, nearest first; empty if none were
+    /// attached.
+    #[must_use]
+    pub fn suggestions(&self) -> &[&'static str] {
+        &self.suggestions
+    }
+}
+
+impl fmt::Display for IdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} {:?}: ", self.type_name, self.value)?;
+        match &self.kind {
+            IdErrorKind::Format(error) => write!(f, "{error}"),
+            IdErrorKind::Namespace(error) => write!(f, "{error}"),
+            IdErrorKind::Hierarchy(error) => write!(f, "{error}"),
+            IdErrorKind::Rejected(reason) => write!(f, "{reason}"),
+            IdErrorKind::Unknown => f.write_str("no such ID exists"),
+        }?;
+        if let Some((last, rest)) = self.suggestions.split_last() {
+            f.write_str("; did you mean ")?;
+            for (i, suggestion) in rest.iter().enumerate() {
+                let separator = if i == 0 { "" } else { ", " };
+                write!(f, "{separator}{suggestion:?}")?;
+            }
+            if !rest.is_empty() {
+                f.write_str(" or ")?;
+            }
+            write!(f, "{last:?}?")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for IdError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            IdErrorKind::Format(error) => Some(error),
+            IdErrorKind::Namespace(error) => Some(error),
+            IdErrorKind::Hierarchy(error) => Some(error),
+            IdErrorKind::Rejected(reason) => Some(reason.as_ref()),
+            IdErrorKind::Unknown => None,
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Built-in ID format rules, configured with `#[interned_id(format = ..., ascii, ...)]`.
+//!
+//! The derive turns the attribute into a constant [`IdFormat`] and checks it in the
+//! generated `try_new`, before any custom `validate` function and before interning.
+
+use std::error::Error;
+use std::fmt;
+
+/// Naming convention an ID must follow.
+///
+/// Every style only allows ASCII letters, digits and its own separator, and requires the ID
+/// to start with a letter. The empty string satisfies every style; use `min_len` to reject it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CaseStyle {
+    /// `fire_ball`: lowercase words joined by single underscores.
+    Snake,
+    /// `fire-ball`: lowercase words joined by single hyphens.
+    Kebab,
+    /// `FIRE_BALL`: uppercase words joined by single underscores.
+    ScreamingSnake,
+    /// `fireBall`: letters and digits, starting lowercase.
+    Camel,
+    /// `FireBall`: letters and digits, starting uppercase.
+    Pascal,
+}
+
+impl CaseStyle {
+    /// The name used in `#[interned_id(format = "...")]`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Snake => "snake_case",
+            Self::Kebab => "kebab-case",
+            Self::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+            Self::Camel => "camelCase",
+            Self::Pascal => "PascalCase",
+        }
+    }
+
+    /// Byte position of the first character that breaks this style, if any.
+    fn violation(self, value: &str) -> Option<usize> {
+        let bytes = value.as_bytes();
+        let separator = match self {
+            Self::Snake | Self::ScreamingSnake => Some(b'_'),
+            Self::Kebab => Some(b'-'),
+            Self::Camel | Self::Pascal => None,
+        };
+
+        for (pos, &byte) in bytes.iter().enumerate() {
+            let allowed = if pos == 0 {
+                match self {
+                    Self::Snake | Self::Kebab | Self::Camel => byte.is_ascii_lowercase(),
+                    Self::ScreamingSnake | Self::Pascal => byte.is_ascii_uppercase(),
+                }
+            } else if Some(byte) == separator {
+                // Separators must sit between two words.
+                bytes[pos - 1] != byte && pos + 1 < bytes.len()
+            } else {
+                match self {
+                    Self::Snake | Self::Kebab => byte.is_ascii_lowercase() || byte.is_ascii_digit(),
+                    Self::ScreamingSnake => byte.is_ascii_uppercase() || byte.is_ascii_digit(),
+                    Self::Camel | Self::Pascal => byte.is_ascii_alphanumeric(),
+                }
+            };
+            if !allowed {
+                return Some(pos);
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for CaseStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A set of format rules for an ID type.
+///
+/// Built with `const` methods so the derive can store it in a constant:
+///
+/// ```
+/// use msg_interned_id::format::{CaseStyle, IdFormat};
+///
+/// const FORMAT: IdFormat = IdFormat::new().case(CaseStyle::Snake).max_len(64).ascii();
+/// assert!(FORMAT.check("fire_ball").is_ok());
+/// assert!(FORMAT.check("FireBall").is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct IdFormat {
+    case: Option<CaseStyle>,
+    ascii: bool,
+    no_leading_digit: bool,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+}
+
+impl IdFormat {
+    /// A format that accepts every string.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            case: None,
+            ascii: false,
+            no_leading_digit: false,
+            min_len: None,
+            max_len: None,
+        }
+    }
+
+    /// Require the given naming convention.
+    #[must_use]
+    pub const fn case(mut self, style: CaseStyle) -> Self {
+        self.case = Some(style);
+        self
+    }
+
+    /// Only allow ASCII characters.
+    #[must_use]
+    pub const fn ascii(mut self) -> Self {
+        self.ascii = true;
+        self
+    }
+
+    /// Reject IDs whose first character is an ASCII digit.
+    #[must_use]
+    pub const fn no_leading_digit(mut self) -> Self {
+        self.no_leading_digit = true;
+        self
+    }
+
+    /// Require at least `len` bytes.
+    #[must_use]
+    pub const fn min_len(mut self, len: usize) -> Self {
+        self.min_len = Some(len);
+        self
+    }
+
+    /// Allow at most `len` bytes.
+    #[must_use]
+    pub const fn max_len(mut self, len: usize) -> Self {
+        self.max_len = Some(len);
+        self
+    }
+
+    /// Check `value` against every rule, reporting the first violation.
+    ///
+    /// Length limits are checked first so oversized input is rejected without scanning it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the violated rule and the byte position where it was violated.
+    pub fn check(&self, value: &str) -> Result<(), FormatError> {
+        self.check_len(value)?;
+        self.check_chars(value, 0)
+    }
+
+    /// Check `value` as a list of segments joined by `separator`, such as `core:fireball`.
+    ///
+    /// Length limits apply to the whole value; the character and case rules apply to each
+    /// segment on its own, so the separator does not have to satisfy them. Positions are
+    /// reported relative to the whole value.
+    ///
+    /// # Errors
+    ///
+    /// Returns the violated rule and the byte position where it was violated.
+    pub fn check_segments(&self, value: &str, separator: char) -> Result<(), FormatError> {
+        self.check_len(value)?;
+        let mut offset = 0;
+        for segment in value.split(separator) {
+            self.check_chars(segment, offset)?;
+            offset += segment.len() + separator.len_utf8();
+        }
+        Ok(())
+    }
+
+    fn check_len(&self, value: &str) -> Result<(), FormatError> {
+        if let Some(max) = self.max_len
+            && value.len() > max
+        {
+            return Err(FormatError::new(FormatRule::MaxLen(max), max));
+        }
+        if let Some(min) = self.min_len
+            && value.len() < min
+        {
+            return Err(FormatError::new(FormatRule::MinLen(min), value.len()));
+        }
+        Ok(())
+    }
+
+    /// Check the character and case rules, reporting positions shifted by `offset`.
+    fn check_chars(&self, value: &str, offset: usize) -> Result<(), FormatError> {
+        if self.ascii
+            && let Some(pos) = value.bytes().position(|byte| !byte.is_ascii())
+        {
+            return Err(FormatError::new(FormatRule::Ascii, offset + pos));
+        }
+        if self.no_leading_digit && value.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(FormatError::new(FormatRule::NoLeadingDigit, offset));
+        }
+        if let Some(style) = self.case
+            && let Some(pos) = style.violation(value)
+        {
+            return Err(FormatError::new(FormatRule::Case(style), offset + pos));
+        }
+        Ok(())
+    }
+}
+
+/// A single rule of an [`IdFormat`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum FormatRule {
+    /// The ID must follow a naming convention.
+    Case(CaseStyle),
+    /// The ID must be ASCII.
+    Ascii,
+    /// The ID must not start with a digit.
+    NoLeadingDigit,
+    /// The ID must be at least this many bytes long.
+    MinLen(usize),
+    /// The ID must be at most this many bytes long.
+    MaxLen(usize),
+}
+
+impl fmt::Display for FormatRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Case(style) => write!(f, "must be {style}"),
+            Self::Ascii => f.write_str("must be ASCII"),
+            Self::NoLeadingDigit => f.write_str("must not start with a digit"),
+            Self::MinLen(len) => write!(f, "must be at least {len} bytes long"),
+            Self::MaxLen(len) => write!(f, "must be at most {len} bytes long"),
+        }
+    }
+}
+
+/// The rule an ID broke and where.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FormatError {
+    rule: FormatRule,
+    position: usize,
+}
+
+impl FormatError {
+    const fn new(rule: FormatRule, position: usize) -> Self {
+        Self { rule, position }
+    }
+
+    /// The violated rule.
+    #[must_use]
+    pub const fn rule(&self) -> FormatRule {
+        self.rule
+    }
+
+    /// Byte offset of the offending character.
+    ///
+    /// For length rules this is the offset where the limit was crossed: the input length for
+    /// `MinLen` and the limit itself for `MaxLen`.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at byte {})", self.rule, self.position)
+    }
+}
+
+impl Error for FormatError {}
@@ -0,0 +1,155 @@
+//! Hashing of interned IDs: a fast per-process hasher for hash maps, and a stable hash for
+//! anything that leaves the process.
+//!
+//! # Hash maps
+//!
+//! An interned ID hashes as its address and length, two integers that are already unique
+//! among the IDs of one type. [`IdHasher`] combines them with a rotate and an XOR and spends
+//! two multiplies in [`finish`](Hasher::finish), where the default SipHash runs several rounds
+//! per integer.
+//!
+//! The hash is only a bucket hint: maps still compare keys with `Eq`, so results stay correct
+//! for any key type, including IDs of different types that happen to share an address. Keys
+//! that hash as bytes, such as `String`, are mixed chunk by chunk and hash reasonably, just
+//! without the speedup. There is no random seed, so it is not meant for keys chosen by an
+//! attacker; interned addresses are not.
+//!
+//! # Stable hashes
+//!
+//! Addresses change from run to run, so pointer-based hashes must never be saved or sent.
+//! [`stable_hash`] depends only on the string content and is the same on every run and
+//! machine, which makes it suitable for replay checksums and lockstep desync detection.
+//! Every ID type computes it once when the ID is interned, and `id.stable_hash()` is then a
+//! lock-free lookup by address rather than a pass over the string.
+
+use std::hash::{BuildHasher, Hasher};
+
+/// FNV-1a offset basis for 64-bit hashes.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a prime for 64-bit hashes.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hash `value` with 64-bit FNV-1a over its UTF-8 bytes.
+///
+/// The algorithm is part of the public contract and will not change: the result only
+/// depends on the bytes of `value`, never on the platform, the run or the crate version.
+/// It is not cryptographic, and distinct strings can collide.
+///
+/// ```
+/// use msg_interned_id::hash::stable_hash;
+///
+/// assert_eq!(stable_hash(""), 0xcbf2_9ce4_8422_2325);
+/// assert_eq!(stable_hash("a"), 0xaf63_dc4c_8601_ec8c);
+/// ```
+#[must_use]
+pub const fn stable_hash(value: &str) -> u64 {
+    let bytes = value.as_bytes();
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+/// Odd constant with well-spread bits, taken from the fractional part of pi.
+///
+/// Strings interned one after another are usually a fixed stride apart, so the keys are
+/// evenly spaced addresses. A single folded multiply leaves such keys lumpy, and which strides
+/// suffer depends on the constant and on the order the words arrive in: `Interned<str>` writes
+/// the address before the length, Bevy's writes the length first. With one multiply, 1024 keys
+/// at a 4096-byte stride fill 748 of 1024 buckets in the first order but only 225 in the
+/// second; the golden-ratio constant of Fibonacci hashing (`0x9e37_79b9_7f4a_7c15`) fills only
+/// 304 at an 8-byte stride. [`IdHasher::finish`] therefore folds twice, after which either
+/// constant fills about 640 at every stride in both orders, as a random hash would;
+/// `test_evenly_spaced_addresses_are_spread` checks the spread.
+const MULTIPLIER: u64 = 0x243f_6a88_85a3_08d3;
+
+/// Multiply into 128 bits and fold the halves together, so every input bit reaches both the
+/// low bits (which pick the bucket) and the high bits (which hash tables use as tags).
+#[inline]
+fn folded_multiply(value: u64, by: u64) -> u64 {
+    let full = u128::from(value) * u128::from(by);
+    (full as u64) ^ ((full >> 64) as u64)
+}
+
+/// A [`Hasher`] tuned for interned IDs. Create it through [`IdBuildHasher`].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct IdHasher {
+    hash: u64,
+}
+
+impl Hasher for IdHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.hash = folded_multiply(self.hash ^ u64::from_le_bytes(word), MULTIPLIER);
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, value: u8) {
+        self.write_u64(u64::from(value));
+    }
+
+    #[inline]
+    fn write_u16(&mut self, value: u16) {
+        self.write_u64(u64::from(value));
+    }
+
+    #[inline]
+    fn write_u32(&mut self, value: u32) {
+        self.write_u64(u64::from(value));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, value: u64) {
+        // Addresses of distinct IDs differ in their low bits, lengths are small: rotating the
+        // previous word out of the way keeps the two from cancelling each other.
+        self.hash = self.hash.rotate_left(32) ^ value;
+    }
+
+    #[inline]
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        folded_multiply(folded_multiply(self.hash, MULTIPLIER), MULTIPLIER)
+    }
+}
+
+/// Builds [`IdHasher`]s. Use it as the `S` parameter of `HashMap`/`HashSet`, or through the
+/// [`IdHashMap`](crate::collections::IdHashMap) and
+/// [`IdHashSet`](crate::collections::IdHashSet) aliases.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use msg_interned_id::InternedId;
+/// use msg_interned_id::hash::IdBuildHasher;
+///
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(standalone)]
+/// pub struct SpellId(msg_interned_id::intern::Interned<str>);
+///
+/// let mut damage: HashMap<SpellId, u32, IdBuildHasher> = HashMap::default();
+/// damage.insert(SpellId::new("fireball"), 40);
+/// assert_eq!(damage[&SpellId::new("fireball")], 40);
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct IdBuildHasher;
+
+impl BuildHasher for IdBuildHasher {
+    type Hasher = IdHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> IdHasher {
+        IdHasher::default()
+    }
+}
@@ -0,0 +1,70 @@
+//! Hierarchical IDs such as `buff.speed.minor`, configured with
+//! `#[interned_id(hierarchical(separator = "."))]`.
+//!
+//! The derive checks every input with [`check`] and builds the generated `parent()`,
+//! `depth()` and `is_descendant_of()` on the helpers below, which only look at the string.
+
+use std::error::Error;
+use std::fmt;
+
+/// Check that every segment of `id` is non-empty.
+///
+/// ```
+/// use msg_interned_id::hierarchy::{HierarchyError, check};
+///
+/// assert!(check("buff.speed.minor", '.').is_ok());
+/// assert_eq!(check("buff..minor", '.'), Err(HierarchyError::EmptySegment(5)));
+/// ```
+///
+/// # Errors
+///
+/// Returns the byte offset of the first empty segment. The empty string is a single empty
+/// segment at offset 0.
+pub fn check(id: &str, separator: char) -> Result<(), HierarchyError> {
+    let mut offset = 0;
+    for segment in id.split(separator) {
+        if segment.is_empty() {
+            return Err(HierarchyError::EmptySegment(offset));
+        }
+        offset += segment.len() + separator.len_utf8();
+    }
+    Ok(())
+}
+
+/// Everything before the last separator, or `None` for a top-level ID.
+#[must_use]
+pub fn parent(id: &str, separator: char) -> Option<&str> {
+    id.rsplit_once(separator).map(|(parent, _)| parent)
+}
+
+/// Number of segments in `id`.
+#[must_use]
+pub fn depth(id: &str, separator: char) -> usize {
+    id.split(separator).count()
+}
+
+/// Whether `id` lies strictly below `ancestor`, segment-wise: `a.b.c` is below `a.b` but
+/// `a.bc` is not.
+#[must_use]
+pub fn is_descendant(id: &str, ancestor: &str, separator: char) -> bool {
+    id.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with(separator))
+}
+
+/// Why a string is not a valid hierarchical ID.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum HierarchyError {
+    /// The segment starting at this byte offset is empty.
+    EmptySegment(usize),
+}
+
+impl fmt::Display for HierarchyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptySegment(pos) => write!(f, "segments must not be empty (at byte {pos})"),
+        }
+    }
+}
+
+impl Error for HierarchyError {}
@@ -0,0 +1,53 @@
+//! The [`InternedId`] trait, implemented by the derive for every ID type.
+
+use crate::IdError;
+
+/// Common interface of every type deriving `InternedId`, for code that is generic over the
+/// ID type, such as [`IdVec`](crate::collections::IdVec).
+///
+/// The derive implements this trait by forwarding to the generated inherent methods of the
+/// same names, so calling them directly needs no import.
+pub trait InternedId: Copy + 'static {
+    /// Name of the ID type, e.g. `"SpellId"`.
+    const TYPE_NAME: &'static str;
+
+    /// The interned string value.
+    fn as_str(&self) -> &'static str;
+
+    /// Dense index of this ID within its type, in creation order.
+    fn index(&self) -> u32;
+
+    /// Content-based hash of this ID, the same on every run and machine.
+    ///
+    /// See [`stable_hash`](crate::hash::stable_hash).
+    fn stable_hash(&self) -> u64;
+
+    /// The ID with the given index, if that many IDs have been created.
+    fn from_index(index: u32) -> Option<Self>;
+
+    /// The existing ID for `id`, without interning it.
+    fn get(id: &str) -> Option<Self>;
+
+    /// Number of distinct IDs created so far.
+    fn count() -> usize;
+
+    /// Turn a string read from outside the program, such as a save file, into an ID.
+    ///
+    /// Follows the same rules as the type's string deserialization: validated IDs reject
+    /// invalid strings and `strict` IDs reject strings that were never interned, suggesting
+    /// the closest existing ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason the string was rejected.
+    fn parse(id: &str) -> Result<Self, IdError>;
+
+    /// Intern every well-known ID declared with `known(...)` up front, so that `all()`,
+    /// `count()` and `from_index` include them before their constants are first used.
+    /// Does nothing for types without declared IDs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if validation rejects a declared value, as accessing its constant would.
+    fn intern_known() {}
+}
@@ -0,0 +1,278 @@
+//! Self-contained string interning behind every derived ID type.
+//!
+//! [`Interned`] mirrors `bevy::ecs::intern::Interned`, so an ID type can wrap either one
+//! without changing how it is used. Every ID type interns through an [`Interner`] generated
+//! for it, whichever `Interned` its field uses.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use self::entries::Entries;
+use self::index::{PointerIndex, Record};
+use crate::hash::stable_hash;
+
+mod entries;
+mod index;
+
+/// A reference to a value that lives for the rest of the program.
+///
+/// Two `Interned` values are equal when they point at the same memory, which is what makes
+/// comparing and hashing interned IDs a pointer-sized operation.
+pub struct Interned<T: ?Sized + 'static>(pub &'static T);
+
+impl<T: ?Sized> Deref for Interned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<T: ?Sized> Clone for Interned<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Interned<T> {}
+
+impl<T: ?Sized> PartialEq for Interned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl<T: ?Sized> Eq for Interned<T> {}
+
+impl<T: ?Sized> Hash for Interned<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self.0, state);
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A thread-safe string interner.
+///
+/// Each distinct string is stored once, leaked unless it was registered from a `'static`
+/// slice, and every later request for the same content returns the same `&'static str`, so
+/// interned values can be compared by pointer. Each string also gets a dense index in
+/// interning order and a [`stable_hash`] computed once when it is interned;
+/// [`iter`](Self::iter), [`index_of`](Self::index_of), [`by_index`](Self::by_index) and
+/// [`stable_hash_of`](Self::stable_hash_of) never take a lock.
+pub struct Interner {
+    lookup: OnceLock<RwLock<Lookup>>,
+    entries: Entries,
+    indices: PointerIndex,
+}
+
+/// The interned strings, findable by content and by stable hash.
+#[derive(Default)]
+struct Lookup {
+    strings: HashSet<&'static str>,
+    /// `None` marks a hash shared by several strings, which identifies none of them.
+    by_stable_hash: HashMap<u64, Option<&'static str>>,
+}
+
+impl Interner {
+    /// Create an empty interner. Usable in `static` items.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            lookup: OnceLock::new(),
+            entries: Entries::new(),
+            indices: PointerIndex::new(),
+        }
+    }
+
+    /// Return the interned copy of `value`, leaking a new one if it has not been seen before.
+    pub fn intern(&self, value: &str) -> Interned<str> {
+        self.insert(value, |value| Box::leak(Box::from(value)))
+    }
+
+    /// Return the interned copy of `value`, registering `value` itself if it has not been
+    /// seen before. Nothing is copied or leaked.
+    ///
+    /// ```
+    /// use msg_interned_id::intern::Interner;
+    ///
+    /// static INTERNER: Interner = Interner::new();
+    /// static NAME: &str = "fireball";
+    ///
+    /// assert!(std::ptr::eq(INTERNER.intern_static(NAME).0, NAME));
+    /// assert!(std::ptr::eq(INTERNER.intern("fireball").0, NAME));
+    /// ```
+    pub fn intern_static(&self, value: &'static str) -> Interned<str> {
+        self.insert(value, |_| value)
+    }
+
+    /// Return the interned copy of `value` if it has been interned before, without ever
+    /// inserting it.
+    #[must_use]
+    pub fn get(&self, value: &str) -> Option<Interned<str>> {
+        let lookup = self
+            .lookup
+            .get()?
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        lookup
+            .strings
+            .get(value)
+            .map(|&existing| Interned(existing))
+    }
+
+    /// The interned string whose [`stable_hash`] is `hash`, without ever inserting anything.
+    ///
+    /// Returns `None` if no interned string has this hash, or if several do.
+    ///
+    /// ```
+    /// use msg_interned_id::hash::stable_hash;
+    /// use msg_interned_id::intern::Interner;
+    ///
+    /// static INTERNER: Interner = Interner::new();
+    /// let fireball = INTERNER.intern("fireball");
+    ///
+    /// assert_eq!(INTERNER.by_stable_hash(stable_hash("fireball")), Some(fireball));
+    /// assert_eq!(INTERNER.by_stable_hash(stable_hash("ice_bolt")), None);
+    /// ```
+    #[must_use]
+    pub fn by_stable_hash(&self, hash: u64) -> Option<Interned<str>> {
+        let lookup = self
+            .lookup
+            .get()?
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        lookup
+            .by_stable_hash
+            .get(&hash)
+            .copied()
+            .flatten()
+            .map(Interned)
+    }
+
+    /// Number of strings interned so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing has been interned yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every string interned so far, in interning order.
+    ///
+    /// The iterator covers the strings interned before it was created; strings interned
+    /// concurrently by other threads may or may not be included.
+    ///
+    /// ```
+    /// use msg_interned_id::intern::Interner;
+    ///
+    /// static INTERNER: Interner = Interner::new();
+    /// INTERNER.intern("b");
+    /// INTERNER.intern("a");
+    /// INTERNER.intern("b");
+    ///
+    /// let all: Vec<&str> = INTERNER.iter().map(|interned| interned.0).collect();
+    /// assert_eq!(all, ["b", "a"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Interned<str>> + '_ {
+        let len = self.entries.len();
+        (0..len).filter_map(|index| self.entries.get(index).map(Interned))
+    }
+
+    /// Index of an interned string, counting from 0 in interning order.
+    ///
+    /// Matches by address, so `value` must be the `&'static str` this interner returned;
+    /// equal content stored elsewhere gives `None`.
+    ///
+    /// ```
+    /// use msg_interned_id::intern::Interner;
+    ///
+    /// static INTERNER: Interner = Interner::new();
+    /// let a = INTERNER.intern("a");
+    /// let b = INTERNER.intern("b");
+    ///
+    /// assert_eq!(INTERNER.index_of(b.0), Some(1));
+    /// assert_eq!(INTERNER.by_index(0), Some(a));
+    /// assert_eq!(INTERNER.index_of(&String::from("a")), None);
+    /// ```
+    #[must_use]
+    pub fn index_of(&self, value: &str) -> Option<u32> {
+        self.indices.get(value).map(|record| record.index)
+    }
+
+    /// The [`stable_hash`] of an interned string, cached when it was interned.
+    ///
+    /// Matches by address like [`index_of`](Self::index_of).
+    ///
+    /// ```
+    /// use msg_interned_id::hash::stable_hash;
+    /// use msg_interned_id::intern::Interner;
+    ///
+    /// static INTERNER: Interner = Interner::new();
+    /// let fireball = INTERNER.intern("fireball");
+    ///
+    /// assert_eq!(INTERNER.stable_hash_of(fireball.0), Some(stable_hash("fireball")));
+    /// ```
+    #[must_use]
+    pub fn stable_hash_of(&self, value: &str) -> Option<u64> {
+        self.indices.get(value).map(|record| record.stable_hash)
+    }
+
+    /// The string interned at `index`, if that many strings have been interned.
+    #[must_use]
+    pub fn by_index(&self, index: u32) -> Option<Interned<str>> {
+        self.entries.get(index as usize).map(Interned)
+    }
+
+    /// Look `value` up, storing the string returned by `store` if it is missing.
+    fn insert(&self, value: &str, store: impl FnOnce(&str) -> &'static str) -> Interned<str> {
+        let lock = self.lookup.get_or_init(Default::default);
+
+        {
+            let lookup = lock.read().unwrap_or_else(PoisonError::into_inner);
+            if let Some(&existing) = lookup.strings.get(value) {
+                return Interned(existing);
+            }
+        }
+
+        let mut lookup = lock.write().unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have interned the value between dropping the read lock and
+        // taking the write lock.
+        if let Some(&existing) = lookup.strings.get(value) {
+            return Interned(existing);
+        }
+        let stored = store(value);
+        // Only the holder of the write lock appends, so entries stay in interning order. The
+        // index is recorded first so anything that can see the entry can also find its index.
+        let record = Record {
+            index: self.entries.next_index(),
+            stable_hash: stable_hash(stored),
+        };
+        self.indices.insert(stored, record);
+        self.entries.push(stored);
+        lookup.strings.insert(stored);
+        lookup
+            .by_stable_hash
+            .entry(record.stable_hash)
+            .and_modify(|existing| *existing = None)
+            .or_insert(Some(stored));
+        Interned(stored)
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
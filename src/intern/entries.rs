@@ -0,0 +1,75 @@
+//! Append-only list of interned strings in interning order.
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of slots in the first segment of [`Entries`], as a power of two.
+const FIRST_SEGMENT_BITS: u32 = 5;
+
+/// Number of segments, enough for every index below `u32::MAX`.
+const SEGMENTS: usize = (u32::BITS + 1 - FIRST_SEGMENT_BITS) as usize;
+
+/// Append-only list of interned strings that can be read without locking.
+///
+/// Slots live in segments that double in size and are never moved once allocated, so a
+/// reader holding an index below the published length can always reach its entry. A single
+/// writer at a time (the holder of the interner's write lock) fills the next slot and then
+/// publishes the new length.
+pub(super) struct Entries {
+    segments: [OnceLock<Box<[OnceLock<&'static str>]>>; SEGMENTS],
+    len: AtomicUsize,
+}
+
+impl Entries {
+    pub(super) const fn new() -> Self {
+        Self {
+            segments: [const { OnceLock::new() }; SEGMENTS],
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    pub(super) fn get(&self, index: usize) -> Option<&'static str> {
+        if index >= self.len() {
+            return None;
+        }
+        let (segment, offset) = Self::locate(index);
+        self.segments[segment].get()?[offset].get().copied()
+    }
+
+    /// Index the next pushed value will get.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every `u32` index below `u32::MAX` is taken.
+    pub(super) fn next_index(&self) -> u32 {
+        u32::try_from(self.len.load(Ordering::Relaxed))
+            .ok()
+            .filter(|&index| index < u32::MAX)
+            .expect("interner is full")
+    }
+
+    /// Append `value` at [`next_index`](Self::next_index). Callers must not push concurrently.
+    pub(super) fn push(&self, value: &'static str) {
+        let index = self.next_index() as usize;
+        let (segment, offset) = Self::locate(index);
+        let slots = self.segments[segment].get_or_init(|| {
+            (0..1usize << (segment as u32 + FIRST_SEGMENT_BITS))
+                .map(|_| OnceLock::new())
+                .collect()
+        });
+        let _ = slots[offset].set(value);
+        self.len.store(index + 1, Ordering::Release);
+    }
+
+    /// Segment and offset of `index`: segment `k` holds `2^(k + FIRST_SEGMENT_BITS)` slots.
+    fn locate(index: usize) -> (usize, usize) {
+        let pos = index + (1 << FIRST_SEGMENT_BITS);
+        let bit = usize::BITS - 1 - pos.leading_zeros();
+        let segment = (bit - FIRST_SEGMENT_BITS) as usize;
+        (segment, pos - (1 << bit))
+    }
+}
@@ -0,0 +1,134 @@
+//! Lock-free map from interned strings, by address, to their index and stable hash.
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+/// Number of slots in the first table, as a power of two.
+const FIRST_TABLE_BITS: u32 = 6;
+
+/// Number of table generations, enough to keep `u32::MAX` entries at most half full.
+const TABLES: usize = (u32::BITS + 2 - FIRST_TABLE_BITS) as usize;
+
+/// One open-addressing slot. An address of zero marks it empty; no string lives at zero.
+struct Slot {
+    addr: AtomicUsize,
+    len: AtomicUsize,
+    index: AtomicU32,
+    stable_hash: AtomicU64,
+}
+
+/// What the index stores about each interned string.
+#[derive(Clone, Copy)]
+pub(super) struct Record {
+    pub(super) index: u32,
+    pub(super) stable_hash: u64,
+}
+
+/// Maps each interned `&'static str` to its [`Record`] by address and length, so a lookup is
+/// a hash of the pointer and a few atomic loads.
+///
+/// A single writer at a time (the holder of the interner's write lock) inserts. Each slot
+/// is written once and published by storing its address last. When a table gets half full
+/// the writer fills the next, twice as large generation and then publishes it; older
+/// generations are kept so readers that still hold them stay valid.
+pub(super) struct PointerIndex {
+    tables: [OnceLock<Box<[Slot]>>; TABLES],
+    current: AtomicUsize,
+    count: AtomicUsize,
+}
+
+impl PointerIndex {
+    pub(super) const fn new() -> Self {
+        Self {
+            tables: [const { OnceLock::new() }; TABLES],
+            current: AtomicUsize::new(0),
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Record of `value`, if it was inserted.
+    pub(super) fn get(&self, value: &str) -> Option<Record> {
+        let table = self.tables[self.current.load(Ordering::Acquire)].get()?;
+        let mask = table.len() - 1;
+        let mut pos = Self::hash(value, table.len());
+        loop {
+            let slot = &table[pos];
+            let addr = slot.addr.load(Ordering::Acquire);
+            if addr == 0 {
+                return None;
+            }
+            if addr == value.as_ptr() as usize && slot.len.load(Ordering::Relaxed) == value.len() {
+                return Some(Self::record(slot));
+            }
+            pos = (pos + 1) & mask;
+        }
+    }
+
+    /// Store `record` for `value`. Callers must not insert concurrently.
+    pub(super) fn insert(&self, value: &'static str, record: Record) {
+        let count = self.count.load(Ordering::Relaxed) + 1;
+        let generation = self.current.load(Ordering::Relaxed);
+        let table = match self.tables[generation].get() {
+            Some(table) if count * 2 <= table.len() => table,
+            Some(full) => {
+                let grown = self.tables[generation + 1].get_or_init(|| Self::table(generation + 1));
+                for slot in full.iter() {
+                    let addr = slot.addr.load(Ordering::Relaxed);
+                    if addr != 0 {
+                        let len = slot.len.load(Ordering::Relaxed);
+                        Self::place(grown, addr, len, Self::record(slot));
+                    }
+                }
+                self.current.store(generation + 1, Ordering::Release);
+                grown
+            }
+            None => self.tables[generation].get_or_init(|| Self::table(generation)),
+        };
+        Self::place(table, value.as_ptr() as usize, value.len(), record);
+        self.count.store(count, Ordering::Relaxed);
+    }
+
+    fn table(generation: usize) -> Box<[Slot]> {
+        (0..1usize << (generation as u32 + FIRST_TABLE_BITS))
+            .map(|_| Slot {
+                addr: AtomicUsize::new(0),
+                len: AtomicUsize::new(0),
+                index: AtomicU32::new(0),
+                stable_hash: AtomicU64::new(0),
+            })
+            .collect()
+    }
+
+    /// Read the record of a slot whose address has been loaded with `Acquire`.
+    fn record(slot: &Slot) -> Record {
+        Record {
+            index: slot.index.load(Ordering::Relaxed),
+            stable_hash: slot.stable_hash.load(Ordering::Relaxed),
+        }
+    }
+
+    fn place(table: &[Slot], addr: usize, len: usize, record: Record) {
+        let mask = table.len() - 1;
+        let mut pos = Self::hash_parts(addr, len, table.len());
+        while table[pos].addr.load(Ordering::Relaxed) != 0 {
+            pos = (pos + 1) & mask;
+        }
+        let slot = &table[pos];
+        slot.len.store(len, Ordering::Relaxed);
+        slot.index.store(record.index, Ordering::Relaxed);
+        slot.stable_hash
+            .store(record.stable_hash, Ordering::Relaxed);
+        slot.addr.store(addr, Ordering::Release);
+    }
+
+    fn hash(value: &str, table_len: usize) -> usize {
+        Self::hash_parts(value.as_ptr() as usize, value.len(), table_len)
+    }
+
+    /// Fibonacci hashing of the address mixed with the length, reduced to the table size.
+    fn hash_parts(addr: usize, len: usize, table_len: usize) -> usize {
+        let key = (addr as u64) ^ (len as u64).rotate_left(32);
+        let bits = table_len.trailing_zeros();
+        (key.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (u64::BITS - bits)) as usize
+    }
+}
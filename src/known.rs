@@ -0,0 +1,63 @@
+//! Well-known IDs declared with `#[interned_id(known(NAME = "value", ...))]`.
+
+use std::fmt;
+use std::ops::Deref;
+
+/// An ID declared up front as an associated constant, such as `SpellId::FIREBALL`.
+///
+/// The ID is interned on first access and cached, so later accesses cost one atomic load.
+/// It dereferences to the ID type, so methods can be called on it directly, and
+/// [`get`](Self::get) returns the ID by value:
+///
+/// ```
+/// use msg_interned_id::InternedId;
+///
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(standalone, known(FIREBALL = "fireball"))]
+/// pub struct SpellId(msg_interned_id::intern::Interned<str>);
+///
+/// assert_eq!(SpellId::FIREBALL.get(), SpellId::new("fireball"));
+/// assert_eq!(SpellId::FIREBALL.as_str(), "fireball");
+/// ```
+pub struct Known<T: 'static> {
+    get: fn() -> &'static T,
+}
+
+impl<T: 'static> Known<T> {
+    /// Wrap a function returning the cached, interned ID.
+    #[must_use]
+    pub const fn new(get: fn() -> &'static T) -> Self {
+        Self { get }
+    }
+
+    /// The ID, interning it on first access.
+    #[must_use]
+    pub fn get(self) -> T
+    where
+        T: Copy,
+    {
+        *(self.get)()
+    }
+}
+
+impl<T: 'static> Deref for Known<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        (self.get)()
+    }
+}
+
+impl<T: 'static> Clone for Known<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Known<T> {}
+
+impl<T: fmt::Debug + 'static> fmt::Debug for Known<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,90 @@
+//! Namespaced `namespace:path` IDs, configured with `#[interned_id(namespaced)]`.
+//!
+//! The derive resolves every input with [`resolve`] before the format rules and any custom
+//! `validate` function run, and splits the interned string with [`split`] for the generated
+//! `namespace()` and `path()` accessors.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// Separator between the namespace and the path, as in `core:fireball`.
+pub const SEPARATOR: char = ':';
+
+/// Check that `id` has the form `namespace:path`, prefixing `default` to a bare path.
+///
+/// Borrows `id` when it already names a namespace.
+///
+/// ```
+/// use msg_interned_id::namespace::{NamespaceError, resolve};
+///
+/// assert_eq!(resolve("mymod:fireball", Some("core")).unwrap(), "mymod:fireball");
+/// assert_eq!(resolve("fireball", Some("core")).unwrap(), "core:fireball");
+/// assert_eq!(resolve("fireball", None), Err(NamespaceError::MissingNamespace));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if either part is empty, if the separator appears more than once, or if
+/// `id` is a bare path and there is no `default` namespace.
+pub fn resolve<'a>(id: &'a str, default: Option<&str>) -> Result<Cow<'a, str>, NamespaceError> {
+    let Some((namespace, path)) = id.split_once(SEPARATOR) else {
+        let Some(default) = default else {
+            return Err(NamespaceError::MissingNamespace);
+        };
+        if id.is_empty() {
+            return Err(NamespaceError::EmptyPath);
+        }
+        return Ok(Cow::Owned(format!("{default}{SEPARATOR}{id}")));
+    };
+
+    if namespace.is_empty() {
+        return Err(NamespaceError::EmptyNamespace);
+    }
+    if path.is_empty() {
+        return Err(NamespaceError::EmptyPath);
+    }
+    if let Some(pos) = path.find(SEPARATOR) {
+        return Err(NamespaceError::ExtraSeparator(namespace.len() + 1 + pos));
+    }
+    Ok(Cow::Borrowed(id))
+}
+
+/// Split a resolved ID into its namespace and path.
+///
+/// Every constructor of a namespaced ID type resolves its input, so the generated
+/// accessors always see both parts. A string without a separator splits into an empty
+/// namespace and the whole string as the path.
+#[must_use]
+pub fn split(id: &str) -> (&str, &str) {
+    id.split_once(SEPARATOR).unwrap_or(("", id))
+}
+
+/// Why a string is not a valid `namespace:path` ID.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum NamespaceError {
+    /// There is no separator and the type has no `default_namespace`.
+    MissingNamespace,
+    /// Nothing precedes the separator.
+    EmptyNamespace,
+    /// Nothing follows the separator.
+    EmptyPath,
+    /// The separator appears again at this byte offset.
+    ExtraSeparator(usize),
+}
+
+impl fmt::Display for NamespaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingNamespace => write!(f, "expected `namespace{SEPARATOR}path`"),
+            Self::EmptyNamespace => f.write_str("namespace must not be empty"),
+            Self::EmptyPath => f.write_str("path must not be empty"),
+            Self::ExtraSeparator(pos) => {
+                write!(f, "unexpected second `{SEPARATOR}` (at byte {pos})")
+            }
+        }
+    }
+}
+
+impl Error for NamespaceError {}
@@ -0,0 +1,86 @@
+//! Input normalization, configured with `#[interned_id(normalize(...))]`.
+//!
+//! Normalization runs before validation and interning, so every spelling that normalizes to
+//! the same string becomes the same ID.
+
+use std::borrow::Cow;
+
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick};
+
+/// A set of normalization steps, applied in a fixed order: trim, lowercase, NFC.
+///
+/// Built with `const` methods so the derive can store it in a constant:
+///
+/// ```
+/// use msg_interned_id::normalize::Normalization;
+///
+/// const NORMALIZE: Normalization = Normalization::new().trim().lowercase();
+/// assert_eq!(NORMALIZE.apply("  Fireball "), "fireball");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Normalization {
+    trim: bool,
+    lowercase: bool,
+    nfc: bool,
+}
+
+impl Normalization {
+    /// A normalization that leaves every string unchanged.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            trim: false,
+            lowercase: false,
+            nfc: false,
+        }
+    }
+
+    /// Strip leading and trailing Unicode whitespace.
+    #[must_use]
+    pub const fn trim(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    /// Convert to lowercase using Unicode case mapping.
+    #[must_use]
+    pub const fn lowercase(mut self) -> Self {
+        self.lowercase = true;
+        self
+    }
+
+    /// Convert to Unicode Normalization Form C, so composed and decomposed spellings of the
+    /// same text (`"é"` and `"e\u{301}"`) are equal.
+    #[must_use]
+    pub const fn nfc(mut self) -> Self {
+        self.nfc = true;
+        self
+    }
+
+    /// Normalize `value`, borrowing it when no step changes anything.
+    #[must_use]
+    pub fn apply<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        let mut value = Cow::Borrowed(value);
+
+        if self.trim {
+            value = match value {
+                Cow::Borrowed(s) => Cow::Borrowed(s.trim()),
+                Cow::Owned(s) => Cow::Owned(s.trim().to_owned()),
+            };
+        }
+        if self.lowercase && value.chars().any(changes_when_lowercased) {
+            value = Cow::Owned(value.to_lowercase());
+        }
+        if self.nfc && is_nfc_quick(value.chars()) != IsNormalized::Yes {
+            value = Cow::Owned(value.nfc().collect());
+        }
+
+        value
+    }
+}
+
+/// Whether lowercasing `c` produces anything other than `c` itself.
+fn changes_when_lowercased(c: char) -> bool {
+    let mut lower = c.to_lowercase();
+    lower.next() != Some(c) || lower.next().is_some()
+}
@@ -0,0 +1,114 @@
+//! Registering ID types with a Bevy `App`.
+//!
+//! Every reflected ID type needs the same setup: its type registration (which the derive
+//! fills with `ReflectDefault` and, with the `serde` feature, `ReflectSerialize` and
+//! `ReflectDeserialize` for scenes) and its well-known IDs. [`InternedIdPlugin`] and
+//! [`InternedIdAppExt::register_interned_id`] do all of it in one call:
+//!
+//! ```
+//! # #[cfg(not(feature = "bevy_0_18"))]
+//! # use {bevy_app_0_17 as bevy_app, bevy_reflect_0_17 as bevy_reflect};
+//! use bevy_app::App;
+//! use msg_interned_id::InternedId;
+//! use msg_interned_id::plugin::{InternedIdAppExt, InternedIdPlugin};
+//!
+//! #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+//! #[interned_id(bevy_reflect = "bevy_reflect", known(FIREBALL = "fireball"))]
+//! pub struct SpellId(bevy_ecs::intern::Interned<str>);
+//!
+//! #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+//! #[interned_id(bevy_reflect = "bevy_reflect")]
+//! pub struct ItemId(bevy_ecs::intern::Interned<str>);
+//!
+//! let mut app = App::new();
+//! app.add_plugins(InternedIdPlugin::<SpellId>::default())
+//!     .register_interned_id::<ItemId>();
+//! assert!(SpellId::contains("fireball"));
+//! ```
+//!
+//! Both work with every enabled Bevy version: with `bevy_0_17` and `bevy_0_18` on, the
+//! plugin is a plugin for both `App` types and registers with that version's reflection.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::InternedId;
+
+/// Registers the ID type `Id` with the app; see [`InternedIdAppExt::register_interned_id`].
+pub struct InternedIdPlugin<Id> {
+    // `fn() -> Id` keeps the plugin `Send + Sync` whatever `Id` is.
+    marker: PhantomData<fn() -> Id>,
+}
+
+impl<Id> InternedIdPlugin<Id> {
+    /// Create the plugin.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Id> Default for InternedIdPlugin<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id> fmt::Debug for InternedIdPlugin<Id> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InternedIdPlugin").finish()
+    }
+}
+
+/// Extension methods for registering ID types with an `App`.
+pub trait InternedIdAppExt {
+    /// Register `Id` in the app's `AppTypeRegistry`, with all the type data its derive
+    /// provides, and intern its well-known IDs (see [`InternedId::intern_known`]).
+    ///
+    /// Calling it again for the same type has no further effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if validation rejects one of the type's well-known IDs, so a broken
+    /// declaration fails at startup instead of on first use.
+    fn register_interned_id<Id: RegisterInternedId<Self>>(&mut self) -> &mut Self;
+}
+
+/// An ID type that can be registered with the `App` type `A`.
+///
+/// Implemented for every ID type that reflects with the `bevy_reflect` of `A`'s Bevy version,
+/// so [`InternedIdAppExt::register_interned_id`] has one signature for all enabled versions.
+pub trait RegisterInternedId<A: ?Sized>: InternedId {
+    /// Register the type with `app`; see [`InternedIdAppExt::register_interned_id`].
+    fn register(app: &mut A);
+}
+
+for_each_bevy_version! {
+    use bevy_app::{App, Plugin};
+    use bevy_reflect::GetTypeRegistration;
+
+    use super::{InternedIdAppExt, InternedIdPlugin, RegisterInternedId};
+    use crate::InternedId;
+
+    impl<Id: InternedId + GetTypeRegistration> RegisterInternedId<App> for Id {
+        fn register(app: &mut App) {
+            Id::intern_known();
+            app.register_type::<Id>();
+        }
+    }
+
+    impl<Id: InternedId + GetTypeRegistration> Plugin for InternedIdPlugin<Id> {
+        fn build(&self, app: &mut App) {
+            app.register_interned_id::<Id>();
+        }
+    }
+
+    impl InternedIdAppExt for App {
+        fn register_interned_id<Id: RegisterInternedId<Self>>(&mut self) -> &mut Self {
+            Id::register(self);
+            self
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Data-driven definitions keyed by ID, such as every spell's stats loaded from a RON file.
+//!
+//! [`IdRegistry`] standardizes the `HashMap<SpellId, SpellData>` that usually sits next to an
+//! ID type. A second definition of the same ID is an error instead of silently replacing the
+//! first, which catches two content files claiming the same name. With a Bevy version feature
+//! enabled the registry is a `Resource` of that version and reflects with `ReflectResource`.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::InternedId;
+use crate::collections::{IdVec, Iter};
+
+/// Definitions of type `Data` for IDs of type `Id`, iterated in the order the IDs were
+/// created.
+///
+/// Stored as an [`IdVec`], so lookups are an index with no hashing. It serializes as a map
+/// from ID strings to definitions; loading rejects keys the ID type rejects and keys that
+/// appear twice.
+///
+/// ```
+/// use msg_interned_id::InternedId;
+/// use msg_interned_id::registry::IdRegistry;
+///
+/// #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// #[interned_id(standalone)]
+/// pub struct SpellId(msg_interned_id::intern::Interned<str>);
+///
+/// #[derive(Debug)]
+/// struct SpellData {
+///     damage: u32,
+/// }
+///
+/// let fireball = SpellId::new("fireball");
+/// let mut spells = IdRegistry::new();
+/// spells.insert(fireball, SpellData { damage: 50 }).unwrap();
+/// assert_eq!(spells[fireball].damage, 50);
+///
+/// let err = spells.insert(fireball, SpellData { damage: 10 }).unwrap_err();
+/// assert_eq!(err.to_string(), r#"SpellId "fireball" is already defined"#);
+/// ```
+pub struct IdRegistry<Id, Data> {
+    definitions: IdVec<Id, Data>,
+}
+
+impl<Id, Data> IdRegistry<Id, Data> {
+    /// Create an empty registry.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            definitions: IdVec::new(),
+        }
+    }
+
+    /// Number of defined IDs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    /// Whether no ID is defined.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    /// Remove every definition.
+    pub fn clear(&mut self) {
+        self.definitions.clear();
+    }
+}
+
+impl<Id: InternedId, Data> IdRegistry<Id, Data> {
+    /// Define `id`, failing if it already has a definition. The existing definition is kept
+    /// and `data` is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DuplicateDefinition`] if `id` is already defined.
+    pub fn insert(&mut self, id: Id, data: Data) -> Result<&mut Data, DuplicateDefinition<Id>> {
+        if self.definitions.contains_key(id) {
+            return Err(DuplicateDefinition { id });
+        }
+        Ok(self.definitions.get_or_insert_with(id, || data))
+    }
+
+    /// Define `id`, replacing and returning any existing definition, as when hot reloading a
+    /// content file.
+    pub fn replace(&mut self, id: Id, data: Data) -> Option<Data> {
+        self.definitions.insert(id, data)
+    }
+
+    /// Move every definition of `other` into this registry, failing without changing either
+    /// if any ID is defined in both.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DuplicateDefinition`] for the first ID, in creation order, defined in both.
+    pub fn merge(&mut self, other: Self) -> Result<(), DuplicateDefinition<Id>> {
+        if let Some(id) = other.ids().find(|&id| self.contains(id)) {
+            return Err(DuplicateDefinition { id });
+        }
+        let mut other = other.definitions;
+        let ids: Vec<Id> = other.keys().collect();
+        for id in ids {
+            if let Some(data) = other.remove(id) {
+                self.definitions.insert(id, data);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove and return the definition of `id`.
+    pub fn remove(&mut self, id: Id) -> Option<Data> {
+        self.definitions.remove(id)
+    }
+
+    /// The definition of `id`.
+    #[must_use]
+    pub fn get(&self, id: Id) -> Option<&Data> {
+        self.definitions.get(id)
+    }
+
+    /// The definition of `id`, mutably.
+    #[must_use]
+    pub fn get_mut(&mut self, id: Id) -> Option<&mut Data> {
+        self.definitions.get_mut(id)
+    }
+
+    /// Whether `id` is defined.
+    #[must_use]
+    pub fn contains(&self, id: Id) -> bool {
+        self.definitions.contains_key(id)
+    }
+
+    /// Every definition with its ID, in index order.
+    pub fn iter(&self) -> Iter<'_, Id, Data> {
+        self.definitions.iter()
+    }
+
+    /// Every defined ID, in index order.
+    pub fn ids(&self) -> impl Iterator<Item = Id> + '_ {
+        self.definitions.keys()
+    }
+}
+
+impl<Id, Data> Default for IdRegistry<Id, Data> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id, Data: Clone> Clone for IdRegistry<Id, Data> {
+    fn clone(&self) -> Self {
+        Self {
+            definitions: self.definitions.clone(),
+        }
+    }
+}
+
+impl<Id: InternedId, Data: PartialEq> PartialEq for IdRegistry<Id, Data> {
+    fn eq(&self, other: &Self) -> bool {
+        self.definitions == other.definitions
+    }
+}
+
+impl<Id: InternedId, Data: Eq> Eq for IdRegistry<Id, Data> {}
+
+impl<Id: InternedId, Data: fmt::Debug> fmt::Debug for IdRegistry<Id, Data> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.definitions, f)
+    }
+}
+
+impl<Id: InternedId, Data> Index<Id> for IdRegistry<Id, Data> {
+    type Output = Data;
+
+    /// # Panics
+    ///
+    /// Panics if `id` is not defined.
+    fn index(&self, id: Id) -> &Data {
+        self.get(id)
+            .unwrap_or_else(|| panic!("{} {:?} is not defined", Id::TYPE_NAME, id.as_str()))
+    }
+}
+
+impl<Id: InternedId, Data> IndexMut<Id> for IdRegistry<Id, Data> {
+    /// # Panics
+    ///
+    /// Panics if `id` is not defined.
+    fn index_mut(&mut self, id: Id) -> &mut Data {
+        self.get_mut(id)
+            .unwrap_or_else(|| panic!("{} {:?} is not defined", Id::TYPE_NAME, id.as_str()))
+    }
+}
+
+impl<'a, Id: InternedId, Data> IntoIterator for &'a IdRegistry<Id, Data> {
+    type Item = (Id, &'a Data);
+    type IntoIter = Iter<'a, Id, Data>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Error returned when an ID is defined twice in an [`IdRegistry`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DuplicateDefinition<Id> {
+    id: Id,
+}
+
+impl<Id: Copy> DuplicateDefinition<Id> {
+    /// The ID that was defined twice.
+    #[must_use]
+    pub fn id(&self) -> Id {
+        self.id
+    }
+}
+
+impl<Id: InternedId> fmt::Display for DuplicateDefinition<Id> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?} is already defined",
+            Id::TYPE_NAME,
+            self.id.as_str()
+        )
+    }
+}
+
+impl<Id: InternedId + fmt::Debug> Error for DuplicateDefinition<Id> {}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::de::{self, MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::IdRegistry;
+    use crate::InternedId;
+
+    /// Serialized as a map from ID strings to definitions.
+    impl<Id: InternedId, Data: Serialize> Serialize for IdRegistry<Id, Data> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.definitions.serialize(serializer)
+        }
+    }
+
+    /// Fails on keys the ID type rejects and on keys that appear more than once.
+    impl<'de, Id: InternedId, Data: Deserialize<'de>> Deserialize<'de> for IdRegistry<Id, Data> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct MapVisitor<Id, Data>(PhantomData<(Id, Data)>);
+
+            impl<'de, Id: InternedId, Data: Deserialize<'de>> Visitor<'de> for MapVisitor<Id, Data> {
+                type Value = IdRegistry<Id, Data>;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a map keyed by {} strings", Id::TYPE_NAME)
+                }
+
+                fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                    let mut registry = IdRegistry::new();
+                    while let Some((key, data)) = map.next_entry::<String, Data>()? {
+                        let id = Id::parse(&key).map_err(de::Error::custom)?;
+                        registry.insert(id, data).map_err(de::Error::custom)?;
+                    }
+                    Ok(registry)
+                }
+            }
+
+            deserializer.deserialize_map(MapVisitor(PhantomData))
+        }
+    }
+}
+
+// A resource of every enabled Bevy version, reflected as an opaque value like `IdVec`: with
+// the `serde` feature the registration carries `ReflectSerialize` and `ReflectDeserialize`,
+// so reflection-based serialization writes the string-keyed map and loading rejects
+// duplicate keys.
+for_each_bevy_version! {
+    use bevy_ecs::reflect::ReflectResource;
+    use bevy_reflect::impl_reflect_opaque;
+    use bevy_reflect::std_traits::ReflectDefault;
+    #[cfg(feature = "serde")]
+    use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+    impl<Id: 'static, Data: 'static> bevy_ecs::resource::Resource for super::IdRegistry<Id, Data> where
+        Self: Send + Sync
+    {
+    }
+
+    #[cfg(not(feature = "serde"))]
+    impl_reflect_opaque!((in msg_interned_id::registry) super::IdRegistry<Id, Data: Clone>(
+        Clone, Default, Resource
+    ));
+    #[cfg(feature = "serde")]
+    impl_reflect_opaque!((in msg_interned_id::registry) super::IdRegistry<
+        Id: crate::InternedId,
+        Data: Clone + serde::Serialize + serde::de::DeserializeOwned
+    >(Clone, Default, Resource, Serialize, Deserialize));
+}
@@ -0,0 +1,79 @@
+//! "Did you mean" suggestions for misspelled IDs, ranked by edit distance.
+//!
+//! Every ID type gets a generated `suggest(query, n)` built on [`closest`]. Strict
+//! deserialization uses it to list the nearest existing IDs when a data file names one that
+//! does not exist:
+//!
+//! ```
+//! use msg_interned_id::InternedId;
+//!
+//! #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+//! #[interned_id(standalone)]
+//! pub struct SpellId(msg_interned_id::intern::Interned<str>);
+//!
+//! let fireball = SpellId::new("fireball");
+//! let _ = SpellId::new("frostbolt");
+//! assert_eq!(SpellId::suggest("firebal", 3), [fireball]);
+//! ```
+
+use crate::InternedId;
+
+/// Number of single-character insertions, deletions and substitutions needed to turn `a`
+/// into `b` (the Levenshtein distance), counted in `char`s.
+///
+/// ```
+/// use msg_interned_id::suggest::edit_distance;
+///
+/// assert_eq!(edit_distance("fireball", "firebal"), 1);
+/// assert_eq!(edit_distance("kitten", "sitting"), 3);
+/// ```
+#[must_use]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // One row of the classic dynamic-programming table, reused for every char of `a`.
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(a_char != b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(diagonal + 1).min(row[j] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Largest edit distance still worth suggesting for `query`: about one edit per three
+/// characters, and at least one.
+fn max_distance(query: &str) -> usize {
+    query.chars().count().max(3) / 3
+}
+
+/// Up to `n` of `candidates` closest to `query`, nearest first and ties in lexical order.
+///
+/// Candidates further than about one edit per three characters of `query` are left out, so
+/// an unrelated query yields no suggestions rather than arbitrary ones. Repeated candidates
+/// are reported once.
+#[must_use]
+pub fn closest<T: InternedId + PartialEq>(
+    query: &str,
+    candidates: impl IntoIterator<Item = T>,
+    n: usize,
+) -> Vec<T> {
+    let limit = max_distance(query);
+    let mut ranked: Vec<(usize, T)> = candidates
+        .into_iter()
+        .filter_map(|id| {
+            let distance = edit_distance(query, id.as_str());
+            (distance <= limit).then_some((distance, id))
+        })
+        .collect();
+    ranked.sort_by(|(a_distance, a), (b_distance, b)| {
+        a_distance
+            .cmp(b_distance)
+            .then_with(|| a.as_str().cmp(b.as_str()))
+    });
+    ranked.dedup_by(|(_, a), (_, b)| a == b);
+    ranked.into_iter().take(n).map(|(_, id)| id).collect()
+}
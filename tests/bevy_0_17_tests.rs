@@ -0,0 +1,150 @@
+//! Tests against Bevy 0.17, built with `--no-default-features --features serde,bevy_0_17` or
+//! with `--features bevy_0_17` alongside the default Bevy 0.18.
+
+// Bevy's derives name `bevy_ecs` and `bevy_reflect`, which are Bevy 0.18 in this test crate.
+use bevy_app_0_17 as bevy_app;
+use bevy_ecs_0_17 as bevy_ecs;
+use bevy_reflect_0_17 as bevy_reflect;
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::{FromReflect, GetTypeRegistration, PartialReflect, Reflect, TypeRegistry};
+use msg_interned_id::InternedId;
+use msg_interned_id::collections::{IdBitSet, IdVec};
+use msg_interned_id::plugin::{InternedIdAppExt, InternedIdPlugin};
+use msg_interned_id::registry::IdRegistry;
+
+#[derive(Component, InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", known(SWORD = "legacy_sword"))]
+pub struct ItemId(bevy_ecs::intern::Interned<str>);
+
+#[derive(Component, Reflect, Default)]
+struct Spellbook {
+    cooldowns: IdVec<SpellId, f32>,
+    unlocked: IdBitSet<SpellId>,
+}
+
+mod ids {
+    use super::*;
+
+    #[test]
+    fn test_reflection() {
+        let id = SpellId::new("legacy_fireball");
+        assert_eq!(SpellId::from_reflect(id.as_partial_reflect()), Some(id));
+        assert_eq!(id.reflect_hash(), Some(id.stable_hash()));
+
+        let cloned = id.reflect_clone().unwrap();
+        assert_eq!(cloned.downcast_ref::<SpellId>(), Some(&id));
+    }
+
+    #[test]
+    fn test_type_registration() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<SpellId>();
+        assert!(registry.get(std::any::TypeId::of::<SpellId>()).is_some());
+        assert!(
+            SpellId::get_type_registration()
+                .data::<bevy_reflect::std_traits::ReflectDefault>()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_component() {
+        let mut world = World::new();
+        let entity = world.spawn(SpellId::new("legacy_component")).id();
+        assert_eq!(
+            world.get::<SpellId>(entity).unwrap().as_str(),
+            "legacy_component"
+        );
+    }
+}
+
+mod collections {
+    use super::*;
+
+    #[test]
+    fn test_collections_reflect() {
+        let fireball = SpellId::new("legacy_book_fireball");
+        let mut book = Spellbook::default();
+        book.cooldowns.insert(fireball, 2.5);
+        book.unlocked.insert(fireball);
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Spellbook>();
+
+        let cloned = book.reflect_clone().unwrap();
+        let cloned = cloned.downcast_ref::<Spellbook>().unwrap();
+        assert_eq!(cloned.cooldowns[fireball], 2.5);
+        assert!(cloned.unlocked.contains(fireball));
+    }
+}
+
+mod registry {
+    use super::*;
+
+    #[test]
+    fn test_registry_resource() {
+        let fireball = SpellId::new("legacy_registry_fireball");
+        let mut spells = IdRegistry::new();
+        spells.insert(fireball, 50_u32).unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(spells);
+        assert_eq!(world.resource::<IdRegistry<SpellId, u32>>()[fireball], 50);
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<IdRegistry<SpellId, u32>>();
+        let registration = registry
+            .get(std::any::TypeId::of::<IdRegistry<SpellId, u32>>())
+            .unwrap();
+        assert!(
+            registration
+                .data::<bevy_ecs::reflect::ReflectResource>()
+                .is_some()
+        );
+    }
+}
+
+mod plugin {
+    use super::*;
+
+    #[test]
+    fn test_plugin_registers_type() {
+        let mut app = bevy_app::App::new();
+        app.add_plugins(InternedIdPlugin::<ItemId>::new());
+
+        assert!(ItemId::contains("legacy_sword"));
+        let registry = app
+            .world()
+            .resource::<bevy_ecs::reflect::AppTypeRegistry>()
+            .read();
+        let registration = registry.get(std::any::TypeId::of::<ItemId>()).unwrap();
+        #[cfg(feature = "serde")]
+        assert!(
+            registration
+                .data::<bevy_reflect::ReflectDeserialize>()
+                .is_some()
+        );
+        assert!(
+            registration
+                .data::<bevy_reflect::ReflectFromReflect>()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_register_interned_id() {
+        let mut app = bevy_app::App::new();
+        app.register_interned_id::<SpellId>();
+
+        let registry = app
+            .world()
+            .resource::<bevy_ecs::reflect::AppTypeRegistry>()
+            .read();
+        assert!(registry.get(std::any::TypeId::of::<SpellId>()).is_some());
+    }
+}
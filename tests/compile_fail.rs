@@ -0,0 +1,7 @@
+//! Compile-fail tests pinning down the diagnostics emitted for invalid `InternedId` input.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
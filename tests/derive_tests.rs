@@ -1,4 +1,7 @@
 //! Comprehensive tests for the InternedId derive macro with Bevy 0.17.
+//!
+//! Serde and reflection tests only build with the matching cargo feature, so the file is
+//! meant to pass under every combination of `serde` and `reflect`.
 
 // Create a facade module that mirrors the bevy crate structure
 // This allows the generated code (which uses bevy::* paths) to work
@@ -8,20 +11,24 @@ mod bevy {
             pub use bevy_ecs::intern::*;
         }
     }
+    #[cfg(feature = "reflect")]
     pub mod reflect {
         pub use bevy_reflect::*;
     }
     pub mod prelude {
         pub use bevy_ecs::prelude::*;
+        #[cfg(feature = "reflect")]
         pub use bevy_reflect::prelude::*;
     }
 }
 
 use bevy::prelude::*;
+#[cfg(feature = "reflect")]
 use bevy::reflect::{
     FromReflect, GetTypeRegistration, PartialReflect, Reflect, ReflectKind, TypePath, Typed,
 };
 use bevy_ecs::world::World;
+#[cfg(feature = "reflect")]
 use bevy_reflect::TypeRegistry;
 use msg_interned_id::InternedId;
 use std::collections::{HashMap, HashSet};
@@ -38,6 +45,24 @@ pub struct OtherId(bevy::ecs::intern::Interned<str>);
 #[derive(Component, InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct ComponentId(bevy::ecs::intern::Interned<str>);
 
+/// ID type that names the Bevy sub-crates directly instead of going through a facade.
+///
+/// Lives in its own module with no imports so the generated code cannot lean on anything
+/// the test file happens to have in scope. It still passes the deprecated `bevy_ecs` key,
+/// which must keep compiling.
+#[allow(deprecated)]
+mod direct_paths {
+    #[derive(msg_interned_id::InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    #[interned_id(
+        bevy_ecs = "bevy_ecs",
+        bevy_reflect = "bevy_reflect",
+        serde = "::serde"
+    )]
+    pub struct DirectId(bevy_ecs::intern::Interned<str>);
+}
+
+use direct_paths::DirectId;
+
 mod core_functionality {
     use super::*;
 
@@ -185,6 +210,7 @@ mod collections {
     }
 }
 
+#[cfg(feature = "serde")]
 mod serde_integration {
     use super::*;
     use serde::{Deserialize, Serialize};
@@ -230,8 +256,39 @@ mod serde_integration {
         assert_eq!(deserialized.id, wrapper.id);
         assert_eq!(deserialized.ids.len(), 2);
     }
+
+    #[test]
+    fn test_deserialize_borrowed_transient_and_owned() {
+        let id = TestId::new("visitor_test");
+
+        // Borrowed straight from the input.
+        assert_eq!(
+            serde_json::from_str::<TestId>(r#""visitor_test""#).unwrap(),
+            id
+        );
+        // Unescaped into a scratch buffer, so only lent for the call.
+        assert_eq!(
+            serde_json::from_str::<TestId>(r#""visitor\u005ftest""#).unwrap(),
+            id
+        );
+        let reader = std::io::Cursor::new(r#""visitor_test""#);
+        assert_eq!(serde_json::from_reader::<_, TestId>(reader).unwrap(), id);
+        // Handed over as an owned `String`.
+        let value = serde_json::Value::String("visitor_test".to_owned());
+        assert_eq!(serde_json::from_value::<TestId>(value).unwrap(), id);
+    }
+
+    #[test]
+    fn test_deserialize_wrong_type() {
+        let err = serde_json::from_str::<TestId>("42").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `42`, expected a TestId string at line 1 column 2"
+        );
+    }
 }
 
+#[cfg(feature = "reflect")]
 mod reflection {
     use super::*;
 
@@ -385,6 +442,7 @@ mod reflection {
     }
 }
 
+#[cfg(feature = "reflect")]
 mod type_registration {
     use super::*;
 
@@ -484,6 +542,38 @@ mod edge_cases {
         assert_eq!(id.as_str().len(), 10000);
     }
 
+    /// Declares inherent methods with the same names as the derive's private helpers.
+    #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    #[interned_id(hierarchical, skip(Default), known(ROOT = "root"))]
+    pub struct HelperNamesId(bevy::ecs::intern::Interned<str>);
+
+    impl HelperNamesId {
+        fn prepare(&self) -> &'static str {
+            self.as_str()
+        }
+
+        fn intern_prepared(&self) -> usize {
+            self.depth()
+        }
+
+        fn intern_unchecked() -> usize {
+            Self::count()
+        }
+
+        fn get_known(&self) -> bool {
+            *self == Self::ROOT.get()
+        }
+    }
+
+    #[test]
+    fn test_user_methods_do_not_collide_with_generated_helpers() {
+        let id = HelperNamesId::new("root.branch");
+        assert_eq!(id.prepare(), "root.branch");
+        assert_eq!(id.intern_prepared(), 2);
+        assert!(HelperNamesId::intern_unchecked() >= 1);
+        assert!(id.parent().unwrap().get_known());
+    }
+
     #[test]
     fn test_match_pattern() {
         let id = TestId::new("fire");
@@ -548,3 +638,40 @@ mod thread_safety {
         }
     }
 }
+
+mod crate_paths {
+    use super::*;
+
+    #[test]
+    fn test_core_with_direct_paths() {
+        let id = DirectId::new("direct");
+        assert_eq!(id, DirectId::new("direct"));
+        assert_eq!(id.as_str(), "direct");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_with_direct_paths() {
+        let id = DirectId::new("direct_serde");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "\"direct_serde\"");
+        let back: DirectId = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, id);
+    }
+
+    #[test]
+    #[cfg(feature = "reflect")]
+    fn test_reflection_with_direct_paths() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<DirectId>();
+
+        let registration = registry.get(std::any::TypeId::of::<DirectId>()).unwrap();
+        assert!(registration.data::<ReflectDefault>().is_some());
+        assert_eq!(DirectId::short_type_path(), "DirectId");
+
+        let mut id = DirectId::new("before");
+        assert!(id.try_apply(&TestId::new("wrong_type")).is_err());
+        id.apply(&DirectId::new("after"));
+        assert_eq!(id.as_str(), "after");
+    }
+}
@@ -0,0 +1,64 @@
+//! Checks that deserializing an existing ID does not allocate.
+//!
+//! Lives in its own test binary because it installs a counting global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use msg_interned_id::InternedId;
+
+/// Counts the allocations made by the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct SpellId(msg_interned_id::intern::Interned<str>);
+
+#[test]
+fn test_existing_id_deserializes_without_allocating() {
+    let json = r#""alloc_fireball""#;
+    let id = SpellId::new("alloc_fireball");
+
+    let mut parsed = None;
+    let count = allocations_during(|| {
+        parsed = Some(serde_json::from_str::<SpellId>(json).unwrap());
+    });
+
+    assert_eq!(parsed, Some(id));
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_new_id_allocates_once_interned() {
+    let json = r#""alloc_new_spell""#;
+    let count = allocations_during(|| {
+        serde_json::from_str::<SpellId>(json).unwrap();
+    });
+
+    assert!(count > 0);
+    assert!(SpellId::contains("alloc_new_spell"));
+}
@@ -0,0 +1,93 @@
+//! Tests for enumerating interned IDs with `all()` and `count()`.
+
+use msg_interned_id::InternedId;
+use std::collections::HashSet;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Only touched by `test_lists_in_creation_order`, so its contents are predictable.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct OrderedId(msg_interned_id::intern::Interned<str>);
+
+/// Only touched by `test_all_during_concurrent_interning`.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct BusyId(msg_interned_id::intern::Interned<str>);
+
+mod enumerate {
+    use super::*;
+
+    #[test]
+    fn test_lists_in_creation_order() {
+        assert_eq!(OrderedId::count(), 0);
+        assert_eq!(OrderedId::all().count(), 0);
+
+        let ids = ["zeta", "alpha", "mid"].map(OrderedId::new);
+        let _ = OrderedId::new("alpha");
+        let _ = OrderedId::get("missing");
+
+        assert_eq!(OrderedId::count(), 3);
+        assert_eq!(OrderedId::all().collect::<Vec<_>>(), ids);
+    }
+
+    #[test]
+    fn test_contains_created_ids() {
+        let id = SpellId::new("listed_spell");
+        assert!(SpellId::all().any(|listed| listed == id));
+        assert!(SpellId::count() >= 1);
+    }
+
+    #[test]
+    fn test_entries_are_the_interned_strings() {
+        let id = SpellId::new("pointer_check");
+        let listed = SpellId::all().find(|listed| *listed == id).unwrap();
+        assert!(std::ptr::eq(listed.as_str(), id.as_str()));
+    }
+}
+
+mod thread_safety {
+    use super::*;
+
+    #[test]
+    fn test_all_during_concurrent_interning() {
+        const WRITERS: usize = 4;
+        const PER_WRITER: usize = 500;
+        let barrier = Arc::new(Barrier::new(WRITERS + 1));
+
+        let writers: Vec<_> = (0..WRITERS)
+            .map(|i| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for j in 0..PER_WRITER {
+                        let _ = BusyId::new(&format!("busy_{i}_{j}"));
+                    }
+                })
+            })
+            .collect();
+
+        barrier.wait();
+        let mut last_seen = 0;
+        while writers.iter().any(|writer| !writer.is_finished()) {
+            let snapshot: Vec<BusyId> = BusyId::all().collect();
+            // Snapshots only grow and never contain duplicates.
+            assert!(snapshot.len() >= last_seen);
+            assert_eq!(
+                snapshot.iter().collect::<HashSet<_>>().len(),
+                snapshot.len()
+            );
+            last_seen = snapshot.len();
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(BusyId::count(), WRITERS * PER_WRITER);
+        assert_eq!(BusyId::all().count(), WRITERS * PER_WRITER);
+    }
+}
@@ -0,0 +1,163 @@
+//! Tests for the built-in format rules (`format`, `ascii`, `min_len`, ...).
+
+use msg_interned_id::format::{CaseStyle, FormatError, FormatRule, IdFormat};
+use msg_interned_id::{IdErrorKind, InternedId};
+
+/// The conventions most content IDs follow.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    standalone,
+    format = "snake_case",
+    ascii,
+    min_len = 1,
+    max_len = 16,
+    skip(Default)
+)]
+pub struct ContentId(msg_interned_id::intern::Interned<str>);
+
+/// Format rules combined with a custom validator, which runs second.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    bevy_reflect = "bevy_reflect",
+    format = "kebab-case",
+    validate = not_reserved
+)]
+pub struct SlugId(bevy_ecs::intern::Interned<str>);
+
+fn not_reserved(id: &str) -> Result<(), &'static str> {
+    if id == "admin" {
+        Err("reserved")
+    } else {
+        Ok(())
+    }
+}
+
+/// Character set rules without a case style.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, ascii, no_leading_digit)]
+pub struct LooseId(msg_interned_id::intern::Interned<str>);
+
+/// The format error behind a rejected `try_new`.
+fn format_error<T: std::fmt::Debug>(result: Result<T, msg_interned_id::IdError>) -> FormatError {
+    match result.unwrap_err().kind() {
+        IdErrorKind::Format(err) => *err,
+        other => panic!("expected a format error, got {other:?}"),
+    }
+}
+
+mod generated {
+    use super::*;
+
+    #[test]
+    fn test_accepts_conforming_ids() {
+        assert_eq!(
+            ContentId::try_new("fire_ball").unwrap().as_str(),
+            "fire_ball"
+        );
+        assert!(ContentId::try_new("ice2").is_ok());
+        assert!(SlugId::try_new("ice-bolt").is_ok());
+        assert!(LooseId::try_new("Any_Thing-1").is_ok());
+    }
+
+    #[test]
+    fn test_reports_rule_and_position() {
+        let err = format_error(ContentId::try_new("fire_Ball"));
+        assert_eq!(err.rule(), FormatRule::Case(CaseStyle::Snake));
+        assert_eq!(err.position(), 5);
+
+        let err = format_error(ContentId::try_new("caf\u{e9}"));
+        assert_eq!(err.rule(), FormatRule::Ascii);
+        assert_eq!(err.position(), 3);
+    }
+
+    #[test]
+    fn test_length_limits() {
+        let err = format_error(ContentId::try_new(""));
+        assert_eq!(err.rule(), FormatRule::MinLen(1));
+
+        let err = format_error(ContentId::try_new("a_very_long_identifier"));
+        assert_eq!(err.rule(), FormatRule::MaxLen(16));
+        assert_eq!(err.position(), 16);
+    }
+
+    #[test]
+    fn test_leading_digit() {
+        let err = format_error(LooseId::try_new("1st"));
+        assert_eq!(err.rule(), FormatRule::NoLeadingDigit);
+        assert_eq!(err.position(), 0);
+    }
+
+    #[test]
+    fn test_error_message() {
+        let err = ContentId::try_new("Fireball").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid ContentId \"Fireball\": must be snake_case (at byte 0)"
+        );
+    }
+
+    #[test]
+    fn test_format_runs_before_validator() {
+        let err = format_error(SlugId::try_new("Admin"));
+        assert_eq!(err.rule(), FormatRule::Case(CaseStyle::Kebab));
+
+        let err = SlugId::try_new("admin").unwrap_err();
+        assert!(matches!(err.kind(), IdErrorKind::Rejected(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_from_str_and_deserialize() {
+        assert!("fire_ball".parse::<ContentId>().is_ok());
+        assert!("fire ball".parse::<ContentId>().is_err());
+
+        let err = serde_json::from_str::<ContentId>("\"FIRE\"").unwrap_err();
+        assert!(err.to_string().contains("must be snake_case (at byte 0)"));
+    }
+}
+
+mod case_styles {
+    use super::*;
+
+    fn check(style: CaseStyle, value: &str) -> Result<(), usize> {
+        IdFormat::new()
+            .case(style)
+            .check(value)
+            .map_err(|err| err.position())
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(check(CaseStyle::Snake, "fire_ball_2"), Ok(()));
+        assert_eq!(check(CaseStyle::Snake, "_fire"), Err(0));
+        assert_eq!(check(CaseStyle::Snake, "fire__ball"), Err(5));
+        assert_eq!(check(CaseStyle::Snake, "fire_"), Err(4));
+        assert_eq!(check(CaseStyle::Snake, "fire-ball"), Err(4));
+        assert_eq!(check(CaseStyle::Snake, "9lives"), Err(0));
+    }
+
+    #[test]
+    fn test_kebab_case() {
+        assert_eq!(check(CaseStyle::Kebab, "fire-ball"), Ok(()));
+        assert_eq!(check(CaseStyle::Kebab, "fire_ball"), Err(4));
+    }
+
+    #[test]
+    fn test_screaming_snake_case() {
+        assert_eq!(check(CaseStyle::ScreamingSnake, "FIRE_BALL"), Ok(()));
+        assert_eq!(check(CaseStyle::ScreamingSnake, "FIRE_bALL"), Err(5));
+    }
+
+    #[test]
+    fn test_camel_and_pascal_case() {
+        assert_eq!(check(CaseStyle::Camel, "fireBall2"), Ok(()));
+        assert_eq!(check(CaseStyle::Camel, "FireBall"), Err(0));
+        assert_eq!(check(CaseStyle::Pascal, "FireBall"), Ok(()));
+        assert_eq!(check(CaseStyle::Pascal, "Fire_Ball"), Err(4));
+    }
+
+    #[test]
+    fn test_empty_string_has_no_case_violation() {
+        assert_eq!(check(CaseStyle::Snake, ""), Ok(()));
+    }
+}
@@ -0,0 +1,59 @@
+//! Tests for the zero-copy `from_static` constructor.
+
+use msg_interned_id::InternedId;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, normalize(trim), format = "snake_case")]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+mod zero_copy {
+    use super::*;
+
+    #[test]
+    fn test_registers_the_static_slice_itself() {
+        static NAME: &str = "meteor_swarm";
+        let id = SpellId::from_static(NAME);
+        assert!(std::ptr::eq(id.as_str(), NAME));
+        assert!(std::ptr::eq(SpellId::new("meteor_swarm").as_str(), NAME));
+    }
+
+    #[test]
+    fn test_falls_back_to_existing_entry() {
+        let existing = SpellId::new("chain_lightning");
+        let from_static = SpellId::from_static("chain_lightning");
+        assert_eq!(from_static, existing);
+        assert!(std::ptr::eq(from_static.as_str(), existing.as_str()));
+    }
+
+    #[test]
+    fn test_validated_static_is_not_copied() {
+        static NAME: &str = "iron_sword";
+        assert!(std::ptr::eq(ItemId::from_static(NAME).as_str(), NAME));
+    }
+}
+
+mod validation {
+    use super::*;
+
+    #[test]
+    fn test_normalized_static_is_copied() {
+        let id = ItemId::from_static("  bronze_shield ");
+        assert_eq!(id.as_str(), "bronze_shield");
+        assert_eq!(id, ItemId::new("bronze_shield"));
+    }
+
+    #[test]
+    fn test_try_from_static_rejects_invalid() {
+        assert!(ItemId::try_from_static("Bronze Shield").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "must be snake_case")]
+    fn test_from_static_panics_on_invalid() {
+        let _ = ItemId::from_static("BronzeShield");
+    }
+}
@@ -0,0 +1,140 @@
+//! Tests for `IdBuildHasher` and the `IdHashMap`/`IdHashSet` aliases.
+
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hasher};
+
+use msg_interned_id::InternedId;
+use msg_interned_id::collections::{IdHashMap, IdHashSet};
+use msg_interned_id::hash::IdBuildHasher;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct EnemyId(msg_interned_id::intern::Interned<str>);
+
+mod maps {
+    use super::*;
+
+    #[test]
+    fn test_map_with_bevy_ids() {
+        let mut map: IdHashMap<SpellId, u32> = IdHashMap::default();
+        map.insert(SpellId::new("hash_fireball"), 1);
+        map.insert(SpellId::new("hash_ice"), 2);
+        map.insert(SpellId::new("hash_fireball"), 3);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&SpellId::new("hash_fireball")], 3);
+        assert_eq!(map[&SpellId::new("hash_ice")], 2);
+    }
+
+    #[test]
+    fn test_set_with_standalone_ids() {
+        let set: IdHashSet<ItemId> = ["potion", "sword", "potion"]
+            .into_iter()
+            .map(ItemId::new)
+            .collect();
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&ItemId::new("sword")));
+        assert!(!set.contains(&ItemId::new("shield")));
+    }
+
+    #[test]
+    fn test_ids_of_different_types_sharing_an_address() {
+        static SHARED: &str = "hash_shared";
+        let item = ItemId::from_static(SHARED);
+        let enemy = EnemyId::from_static(SHARED);
+        assert!(std::ptr::eq(item.as_str(), enemy.as_str()));
+
+        // Same address, so same hash; `Eq` keeps the keys apart.
+        let mut map: IdHashMap<(Option<ItemId>, Option<EnemyId>), &str> = IdHashMap::default();
+        map.insert((Some(item), None), "item");
+        map.insert((None, Some(enemy)), "enemy");
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&(Some(item), None)], "item");
+        assert_eq!(map[&(None, Some(enemy))], "enemy");
+    }
+
+    #[test]
+    fn test_non_id_keys() {
+        let mut map: IdHashMap<String, usize> = IdHashMap::default();
+        for i in 0..100 {
+            map.insert(format!("key_{i}"), i);
+        }
+
+        assert_eq!(map.len(), 100);
+        assert_eq!(map["key_42"], 42);
+    }
+}
+
+mod distribution {
+    use super::*;
+
+    #[test]
+    fn test_distinct_ids_get_distinct_hashes() {
+        let hashes: HashSet<u64> = (0..1000)
+            .map(|i| IdBuildHasher.hash_one(ItemId::new(&format!("hash_item_{i}"))))
+            .collect();
+
+        assert_eq!(hashes.len(), 1000);
+    }
+
+    #[test]
+    fn test_low_bits_are_spread() {
+        // Hash tables pick buckets from the low bits, which are all zero in aligned addresses
+        // unless the hasher mixes them in.
+        let buckets: HashSet<u64> = (0..256)
+            .map(|i| IdBuildHasher.hash_one(SpellId::new(&format!("hash_spell_{i}"))) & 0xff)
+            .collect();
+
+        assert!(
+            buckets.len() > 128,
+            "only {} of 256 buckets used",
+            buckets.len()
+        );
+    }
+
+    #[test]
+    fn test_evenly_spaced_addresses_are_spread() {
+        // Hashes the address and length in both orders `Interned` types write them, at the
+        // strides an allocator typically leaves between small strings.
+        for stride in [8, 16, 32, 64, 4096] {
+            for address_first in [true, false] {
+                let buckets: HashSet<u64> = (0..1024)
+                    .map(|i| {
+                        let address = 0x1000_0000 + i * stride;
+                        let mut hasher = IdBuildHasher.build_hasher();
+                        if address_first {
+                            hasher.write_usize(address);
+                            hasher.write_usize(12);
+                        } else {
+                            hasher.write_usize(12);
+                            hasher.write_usize(address);
+                        }
+                        hasher.finish() & 1023
+                    })
+                    .collect();
+
+                assert!(
+                    buckets.len() > 600,
+                    "stride {stride}, address first: {address_first}: only {} of 1024 buckets used",
+                    buckets.len()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash_is_deterministic() {
+        let id = SpellId::new("hash_stable");
+        assert_eq!(IdBuildHasher.hash_one(id), IdBuildHasher.hash_one(id));
+    }
+}
@@ -0,0 +1,184 @@
+//! Tests for hierarchical IDs (`hierarchical(separator = ...)`).
+
+use msg_interned_id::hierarchy::{self, HierarchyError};
+use msg_interned_id::{IdErrorKind, InternedId};
+
+/// Dotted status effect IDs, e.g. `buff.speed.minor`.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    bevy_reflect = "bevy_reflect",
+    hierarchical,
+    format = "snake_case",
+    skip(Default)
+)]
+pub struct EffectId(bevy_ecs::intern::Interned<str>);
+
+/// Slash-separated config keys.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, hierarchical(separator = "/"), skip(Default))]
+pub struct ConfigKey(msg_interned_id::intern::Interned<str>);
+
+/// Talents always sit inside a tree, so a bare top-level segment is not a talent.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, hierarchical, validate = in_a_tree, skip(Default))]
+pub struct TalentId(msg_interned_id::intern::Interned<str>);
+
+fn in_a_tree(id: &str) -> Result<(), &'static str> {
+    if id.contains('.') {
+        Ok(())
+    } else {
+        Err("must name a talent tree")
+    }
+}
+
+mod navigation {
+    use super::*;
+
+    #[test]
+    fn test_parent_walks_up_to_the_root() {
+        let id = EffectId::new("buff.speed.minor");
+        let parent = id.parent().unwrap();
+        assert_eq!(parent.as_str(), "buff.speed");
+        assert_eq!(parent.parent().unwrap().as_str(), "buff");
+        assert_eq!(parent.parent().unwrap().parent(), None);
+    }
+
+    #[test]
+    fn test_parent_is_interned_in_the_same_interner() {
+        let parent = EffectId::new("buff.speed.minor").parent().unwrap();
+        let direct = EffectId::new("buff.speed");
+        assert_eq!(parent, direct);
+        assert!(std::ptr::eq(parent.as_str(), direct.as_str()));
+    }
+
+    #[test]
+    fn test_parent_rejected_by_validation() {
+        let talent = TalentId::new("fire.ignite.spread");
+        assert_eq!(talent.parent(), Some(TalentId::new("fire.ignite")));
+
+        let count = TalentId::count();
+        assert_eq!(TalentId::new("fire.ignite").parent(), None);
+        assert!(TalentId::try_new("fire").is_err());
+        assert!(!TalentId::contains("fire"));
+        assert_eq!(TalentId::count(), count);
+    }
+
+    #[test]
+    fn test_child_appends_a_segment() {
+        let child = EffectId::new("buff.speed").child("major");
+        assert_eq!(child, EffectId::new("buff.speed.major"));
+        assert_eq!(child.parent(), Some(EffectId::new("buff.speed")));
+    }
+
+    #[test]
+    #[should_panic(expected = "segments must not be empty")]
+    fn test_child_rejects_empty_segment() {
+        let _ = EffectId::new("buff").child("");
+    }
+
+    #[test]
+    fn test_segments_and_depth() {
+        let id = EffectId::new("buff.speed.minor");
+        assert_eq!(
+            id.segments().collect::<Vec<_>>(),
+            ["buff", "speed", "minor"]
+        );
+        assert_eq!(id.depth(), 3);
+        assert_eq!(EffectId::new("buff").depth(), 1);
+    }
+
+    #[test]
+    fn test_custom_separator() {
+        let key = ConfigKey::new("audio/music/volume");
+        assert_eq!(key.parent(), Some(ConfigKey::new("audio/music")));
+        assert_eq!(key.child("max").as_str(), "audio/music/volume/max");
+        assert_eq!(ConfigKey::new("a.b").depth(), 1);
+    }
+}
+
+mod descendants {
+    use super::*;
+
+    #[test]
+    fn test_is_descendant_of_ancestors() {
+        let id = EffectId::new("buff.speed.minor");
+        assert!(id.is_descendant_of(&EffectId::new("buff.speed")));
+        assert!(id.is_descendant_of(&EffectId::new("buff")));
+    }
+
+    #[test]
+    fn test_is_not_descendant_of_itself_or_siblings() {
+        let id = EffectId::new("buff.speed");
+        assert!(!id.is_descendant_of(&id));
+        assert!(!id.is_descendant_of(&EffectId::new("buff.speed.minor")));
+        assert!(!id.is_descendant_of(&EffectId::new("buff.spe")));
+        assert!(!EffectId::new("buff.speedy").is_descendant_of(&id));
+    }
+}
+
+mod validation {
+    use super::*;
+    use msg_interned_id::format::{CaseStyle, FormatRule};
+
+    fn hierarchy_error<T: std::fmt::Debug>(
+        result: Result<T, msg_interned_id::IdError>,
+    ) -> HierarchyError {
+        match result.unwrap_err().kind() {
+            IdErrorKind::Hierarchy(err) => *err,
+            other => panic!("expected a hierarchy error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_rejects_empty_segments() {
+        assert_eq!(
+            hierarchy_error(EffectId::try_new("")),
+            HierarchyError::EmptySegment(0)
+        );
+        assert_eq!(
+            hierarchy_error(EffectId::try_new(".buff")),
+            HierarchyError::EmptySegment(0)
+        );
+        assert_eq!(
+            hierarchy_error(EffectId::try_new("buff.")),
+            HierarchyError::EmptySegment(5)
+        );
+        assert_eq!(
+            hierarchy_error(ConfigKey::try_new("a//b")),
+            HierarchyError::EmptySegment(2)
+        );
+    }
+
+    #[test]
+    fn test_format_rules_apply_per_segment() {
+        assert!(EffectId::try_new("buff.speed_up").is_ok());
+
+        let err = EffectId::try_new("buff.Speed").unwrap_err();
+        let IdErrorKind::Format(format) = err.kind() else {
+            panic!("expected a format error, got {:?}", err.kind());
+        };
+        assert_eq!(format.rule(), FormatRule::Case(CaseStyle::Snake));
+        assert_eq!(format.position(), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_rejects_malformed_ids() {
+        let id: EffectId = serde_json::from_str(r#""buff.speed""#).unwrap();
+        assert_eq!(id.depth(), 2);
+        assert!(serde_json::from_str::<EffectId>(r#""buff..speed""#).is_err());
+    }
+}
+
+mod runtime {
+    use super::*;
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(hierarchy::parent("a.b.c", '.'), Some("a.b"));
+        assert_eq!(hierarchy::parent("a", '.'), None);
+        assert_eq!(hierarchy::depth("a.b.c", '.'), 3);
+        assert!(hierarchy::is_descendant("a.b", "a", '.'));
+        assert!(!hierarchy::is_descendant("ab", "a", '.'));
+    }
+}
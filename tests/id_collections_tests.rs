@@ -0,0 +1,251 @@
+//! Tests for the dense `IdVec` and `IdBitSet` collections.
+
+use std::any::TypeId;
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy_reflect::{
+    DynamicStruct, FromReflect, GetTypeRegistration, PartialReflect, Reflect, ReflectRef,
+    TypeRegistry,
+};
+use msg_interned_id::InternedId;
+use msg_interned_id::collections::{IdBitSet, IdVec};
+use serde::de::DeserializeSeed;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Only ever holds the IDs created by `test_iterates_in_index_order`.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct OrderedId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, format = "snake_case")]
+pub struct RecipeId(msg_interned_id::intern::Interned<str>);
+
+/// Collections inside ECS data, as they would be used in a game.
+#[derive(Component, Reflect, Default)]
+struct Spellbook {
+    cooldowns: IdVec<SpellId, f32>,
+    unlocked: IdBitSet<SpellId>,
+}
+
+mod id_vec {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let fireball = SpellId::new("vec_fireball");
+        let ice = SpellId::new("vec_ice");
+        let mut map = IdVec::new();
+
+        assert_eq!(map.insert(fireball, 1.0), None);
+        assert_eq!(map.insert(fireball, 2.0), Some(1.0));
+        assert_eq!(map.get(fireball), Some(&2.0));
+        assert_eq!(map.get(ice), None);
+        assert_eq!(map.len(), 1);
+
+        map[fireball] += 1.0;
+        *map.get_or_insert_with(ice, || 5.0) -= 1.0;
+        assert_eq!(map[fireball], 3.0);
+        assert_eq!(map[ice], 4.0);
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.remove(fireball), Some(3.0));
+        assert_eq!(map.remove(fireball), None);
+        assert!(!map.contains_key(fireball));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_iterates_in_index_order() {
+        let [c, a, b] = ["c", "a", "b"].map(OrderedId::new);
+        let map: IdVec<OrderedId, u32> = [(b, 2), (a, 1), (c, 3)].into_iter().collect();
+
+        assert_eq!(map.iter().collect::<Vec<_>>(), [(c, &3), (a, &1), (b, &2)]);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [c, a, b]);
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), [3, 1, 2]);
+        assert_eq!(map.iter().len(), 3);
+    }
+
+    #[test]
+    fn test_retain_and_equality() {
+        let ids = ["eq_a", "eq_b", "eq_c"].map(SpellId::new);
+        let mut map: IdVec<SpellId, i32> = ids.iter().copied().zip([1, 2, 3]).collect();
+        map.retain(|_, value| *value != 2);
+
+        let expected: IdVec<SpellId, i32> = [(ids[0], 1), (ids[2], 3)].into_iter().collect();
+        assert_eq!(map, expected);
+        assert_eq!(map.len(), 2);
+
+        // Equal contents compare equal regardless of trailing capacity.
+        let mut longer = expected.clone();
+        let extra = SpellId::new("eq_extra");
+        longer.insert(extra, 0);
+        longer.remove(extra);
+        assert_eq!(longer, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "no value for SpellId \"vec_missing\"")]
+    fn test_index_panics_for_missing_id() {
+        let map: IdVec<SpellId, f32> = IdVec::new();
+        let _ = map[SpellId::new("vec_missing")];
+    }
+
+    #[test]
+    fn test_debug_uses_strings() {
+        let map: IdVec<SpellId, u8> = [(SpellId::new("vec_debug"), 1)].into_iter().collect();
+        assert_eq!(format!("{map:?}"), r#"{"vec_debug": 1}"#);
+    }
+}
+
+mod id_bit_set {
+    use super::*;
+
+    #[test]
+    fn test_insert_contains_remove() {
+        let a = SpellId::new("bits_a");
+        let b = SpellId::new("bits_b");
+        let mut set = IdBitSet::new();
+
+        assert!(set.insert(a));
+        assert!(!set.insert(a));
+        assert!(set.contains(a));
+        assert!(!set.contains(b));
+        assert_eq!(set.len(), 1);
+
+        assert!(!set.remove(b));
+        assert!(set.remove(a));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_iterates_across_words_in_index_order() {
+        let ids: Vec<SpellId> = (0..200)
+            .map(|i| SpellId::new(&format!("bits_{i}")))
+            .collect();
+        let picked: Vec<SpellId> = ids.iter().copied().step_by(7).collect();
+        let set: IdBitSet<SpellId> = picked.iter().rev().copied().collect();
+
+        assert_eq!(set.iter().collect::<Vec<_>>(), picked);
+        assert_eq!(set.iter().len(), picked.len());
+    }
+}
+
+mod serde_support {
+    use super::*;
+
+    #[test]
+    fn test_id_vec_serializes_by_string() {
+        let map: IdVec<RecipeId, u32> = [(RecipeId::new("bread"), 2), (RecipeId::new("cake"), 5)]
+            .into_iter()
+            .collect();
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"bread":2,"cake":5}"#);
+        assert_eq!(
+            serde_json::from_str::<IdVec<RecipeId, u32>>(&json).unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn test_bit_set_serializes_by_string() {
+        let set: IdBitSet<RecipeId> = [RecipeId::new("soup"), RecipeId::new("stew")]
+            .into_iter()
+            .collect();
+
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(json, r#"["soup","stew"]"#);
+        assert_eq!(
+            serde_json::from_str::<IdBitSet<RecipeId>>(&json).unwrap(),
+            set
+        );
+    }
+
+    #[test]
+    fn test_deserialize_creates_unseen_ids() {
+        let map: IdVec<RecipeId, u32> = serde_json::from_str(r#"{"pie_from_disk":1}"#).unwrap();
+        assert_eq!(map[RecipeId::new("pie_from_disk")], 1);
+    }
+
+    #[test]
+    fn test_deserialize_validates_keys() {
+        let err = serde_json::from_str::<IdVec<RecipeId, u32>>(r#"{"Bad Key":1}"#).unwrap_err();
+        assert!(err.to_string().contains("must be snake_case"), "{err}");
+        assert!(serde_json::from_str::<IdBitSet<RecipeId>>(r#"["Bad Key"]"#).is_err());
+    }
+}
+
+mod reflection {
+    use super::*;
+
+    #[test]
+    fn test_collections_in_a_component() {
+        let fireball = SpellId::new("reflect_fireball");
+        let mut world = World::new();
+        let mut book = Spellbook::default();
+        book.cooldowns.insert(fireball, 1.5);
+        book.unlocked.insert(fireball);
+        let entity = world.spawn(book).id();
+
+        let book = world.get::<Spellbook>(entity).unwrap();
+        assert_eq!(book.cooldowns[fireball], 1.5);
+        assert!(book.unlocked.contains(fireball));
+    }
+
+    #[test]
+    fn test_reflected_as_opaque_values() {
+        let mut book = Spellbook::default();
+        book.cooldowns.insert(SpellId::new("reflect_ice"), 3.0);
+        book.unlocked.insert(SpellId::new("reflect_ice"));
+
+        assert!(matches!(
+            book.cooldowns.reflect_ref(),
+            ReflectRef::Opaque(_)
+        ));
+        assert!(matches!(book.unlocked.reflect_ref(), ReflectRef::Opaque(_)));
+        assert!(book.cooldowns.try_apply(&DynamicStruct::default()).is_err());
+
+        let clone = book.cooldowns.reflect_clone().unwrap();
+        let clone = clone.downcast_ref::<IdVec<SpellId, f32>>().unwrap();
+        assert_eq!(*clone, book.cooldowns);
+    }
+
+    #[test]
+    fn test_reflected_serde_uses_id_strings() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Spellbook>();
+
+        let frost = SpellId::new("reflect_frost");
+        let mut cooldowns = IdVec::new();
+        cooldowns.insert(frost, 4.0_f32);
+        let json =
+            serde_json::to_string(&TypedReflectSerializer::new(&cooldowns, &registry)).unwrap();
+        assert_eq!(json, r#"{"reflect_frost":4.0}"#);
+
+        let registration = registry.get(TypeId::of::<IdVec<SpellId, f32>>()).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(IdVec::from_reflect(&*value), Some(cooldowns));
+
+        let unlocked: IdBitSet<SpellId> = [frost].into_iter().collect();
+        let json =
+            serde_json::to_string(&TypedReflectSerializer::new(&unlocked, &registry)).unwrap();
+        assert_eq!(json, r#"["reflect_frost"]"#);
+    }
+
+    #[test]
+    fn test_register_types() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Spellbook>();
+        assert!(registry.contains(TypeId::of::<IdVec<SpellId, f32>>()));
+        assert!(registry.contains(TypeId::of::<IdBitSet<SpellId>>()));
+        let _ = IdBitSet::<SpellId>::get_type_registration();
+    }
+}
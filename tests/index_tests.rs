@@ -0,0 +1,132 @@
+//! Tests for dense per-type indices (`index()` / `from_index()`).
+
+use msg_interned_id::InternedId;
+use msg_interned_id::intern::Interner;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Only touched by `test_indices_follow_creation_order`.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct OrderedId(msg_interned_id::intern::Interned<str>);
+
+/// Only touched by `test_from_index_out_of_range`, so no other test can fill the index it
+/// expects to be empty.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct SparseId(msg_interned_id::intern::Interned<str>);
+
+/// Only touched by `test_indices_under_concurrent_interning`.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct BusyId(msg_interned_id::intern::Interned<str>);
+
+mod indices {
+    use super::*;
+
+    #[test]
+    fn test_indices_follow_creation_order() {
+        let a = OrderedId::new("a");
+        let b = OrderedId::from_static("b");
+        let c = OrderedId::new("c");
+        assert_eq!([a.index(), b.index(), c.index()], [0, 1, 2]);
+        assert_eq!(OrderedId::new("b").index(), 1);
+    }
+
+    #[test]
+    fn test_from_index_round_trips() {
+        let id = SpellId::new("round_trip");
+        let back = SpellId::from_index(id.index()).unwrap();
+        assert_eq!(back, id);
+        assert!(std::ptr::eq(back.as_str(), id.as_str()));
+    }
+
+    #[test]
+    fn test_from_index_out_of_range() {
+        let id = SparseId::new("only");
+        assert_eq!(SparseId::from_index(id.index() + 1), None);
+        assert_eq!(SparseId::from_index(u32::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_indices_match_all() {
+        let _ = SpellId::new("listed");
+        for (expected, id) in SpellId::all().enumerate() {
+            assert_eq!(id.index() as usize, expected);
+        }
+    }
+
+    #[test]
+    fn test_empty_string_has_an_index() {
+        let empty = SpellId::default();
+        assert_eq!(SpellId::from_index(empty.index()), Some(empty));
+    }
+
+    #[test]
+    fn test_many_ids_across_table_growth() {
+        let ids: Vec<SpellId> = (0..5000)
+            .map(|i| SpellId::new(&format!("bulk_{i}")))
+            .collect();
+        for id in &ids {
+            assert_eq!(SpellId::from_index(id.index()), Some(*id));
+        }
+        let indices: Vec<u32> = ids.iter().map(SpellId::index).collect();
+        assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
+
+mod thread_safety {
+    use super::*;
+
+    #[test]
+    fn test_indices_under_concurrent_interning() {
+        const THREADS: usize = 8;
+        const PER_THREAD: usize = 1000;
+        let barrier = Arc::new(Barrier::new(THREADS));
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for j in 0..PER_THREAD {
+                        // Half the names are shared between threads.
+                        let name = if j % 2 == 0 {
+                            format!("shared_{j}")
+                        } else {
+                            format!("own_{i}_{j}")
+                        };
+                        let id = BusyId::new(&name);
+                        assert_eq!(BusyId::from_index(id.index()), Some(id));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let count = BusyId::count();
+        assert_eq!(count, PER_THREAD / 2 + THREADS * PER_THREAD / 2);
+        for index in 0..count as u32 {
+            assert_eq!(BusyId::from_index(index).unwrap().index(), index);
+        }
+    }
+}
+
+mod interner {
+    use super::*;
+
+    #[test]
+    fn test_index_is_by_address() {
+        static INTERNER: Interner = Interner::new();
+        let interned = INTERNER.intern("value");
+        assert_eq!(INTERNER.index_of(interned.0), Some(0));
+        assert_eq!(INTERNER.index_of(&String::from("value")), None);
+        assert_eq!(INTERNER.index_of(&interned.0[1..]), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Tests for well-known IDs declared with `known(...)`.
+
+use msg_interned_id::InternedId;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    bevy_reflect = "bevy_reflect",
+    known(FIREBALL = "fireball", ICE_BOLT = "ice_bolt")
+)]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Known IDs go through the same normalization and validation as `new`.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    standalone,
+    normalize(lowercase),
+    format = "snake_case",
+    validate = not_reserved,
+    known(HEAL = "Heal", BROKEN = "reserved"),
+    skip(Default)
+)]
+pub struct AbilityId(msg_interned_id::intern::Interned<str>);
+
+/// The derive checks the built-in rules on `known(...)` values, but not custom validation.
+fn not_reserved(id: &str) -> Result<(), &'static str> {
+    if id == "reserved" {
+        Err("is reserved")
+    } else {
+        Ok(())
+    }
+}
+
+/// Only ever looked up, never accessed through its constants.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, known(FIRE = "fire", WATER = "water"))]
+pub struct SchoolId(msg_interned_id::intern::Interned<str>);
+
+mod constants {
+    use super::*;
+
+    #[test]
+    fn test_constant_matches_new() {
+        assert_eq!(SpellId::FIREBALL.get(), SpellId::new("fireball"));
+        assert_eq!(*SpellId::ICE_BOLT, SpellId::new("ice_bolt"));
+    }
+
+    #[test]
+    fn test_constant_shares_the_interned_string() {
+        let from_new = SpellId::new("ice_bolt");
+        assert!(std::ptr::eq(SpellId::ICE_BOLT.as_str(), from_new.as_str()));
+        assert!(std::ptr::eq(
+            SpellId::ICE_BOLT.as_str(),
+            SpellId::ICE_BOLT.as_str()
+        ));
+    }
+
+    #[test]
+    fn test_constant_derefs_to_the_id() {
+        assert_eq!(SpellId::FIREBALL.to_string(), "fireball");
+        assert_eq!(
+            format!("{:?}", SpellId::FIREBALL),
+            format!("{:?}", SpellId::new("fireball"))
+        );
+    }
+
+    #[test]
+    fn test_constant_in_match_guard() {
+        let id = SpellId::new("fireball");
+        let damage = match id {
+            id if id == *SpellId::FIREBALL => 10,
+            id if id == *SpellId::ICE_BOLT => 5,
+            _ => 0,
+        };
+        assert_eq!(damage, 10);
+    }
+
+    #[test]
+    fn test_constant_is_normalized() {
+        assert_eq!(AbilityId::HEAL.as_str(), "heal");
+    }
+
+    #[test]
+    #[should_panic(expected = "is reserved")]
+    fn test_rejected_constant_panics_on_access() {
+        let _ = AbilityId::BROKEN.get();
+    }
+
+    #[test]
+    fn test_first_access_from_many_threads() {
+        let ids: Vec<SpellId> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| SpellId::FIREBALL.get()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert!(
+            ids.iter()
+                .all(|id| std::ptr::eq(id.as_str(), ids[0].as_str()))
+        );
+    }
+}
+
+mod table {
+    use super::*;
+
+    #[test]
+    fn test_lists_declared_ids_in_order() {
+        assert_eq!(
+            SpellId::KNOWN,
+            &[("FIREBALL", "fireball"), ("ICE_BOLT", "ice_bolt")]
+        );
+    }
+
+    #[test]
+    fn test_lists_declared_values_before_normalization() {
+        assert_eq!(AbilityId::KNOWN[0], ("HEAL", "Heal"));
+    }
+}
+
+mod lookup {
+    use super::*;
+
+    #[test]
+    fn test_get_finds_declared_ids_before_first_access() {
+        assert_eq!(SchoolId::count(), 0);
+        let fire = SchoolId::get("fire").unwrap();
+        assert!(std::ptr::eq(fire.as_str(), SchoolId::FIRE.as_str()));
+        assert_eq!(SchoolId::count(), 1);
+        assert_eq!(SchoolId::get("earth"), None);
+    }
+
+    #[test]
+    fn test_get_prepares_declared_values() {
+        assert_eq!(AbilityId::get("HEAL"), Some(AbilityId::HEAL.get()));
+        // The invalid `BROKEN` declaration is skipped instead of panicking.
+        assert_eq!(AbilityId::get("reserved"), None);
+    }
+}
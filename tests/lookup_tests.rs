@@ -0,0 +1,108 @@
+//! Tests for lookup without interning: `get`, `contains` and `strict` conversions.
+
+use msg_interned_id::{IdErrorKind, InternedId};
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Conversions from untrusted input never create new IDs.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, strict, normalize(trim, lowercase))]
+pub struct CommandId(msg_interned_id::intern::Interned<str>);
+
+/// Lookups see through the default namespace.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    standalone,
+    strict,
+    namespaced,
+    default_namespace = "core",
+    skip(Default)
+)]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+mod get {
+    use super::*;
+
+    #[test]
+    fn test_finds_existing_ids() {
+        let id = SpellId::new("fireball");
+        assert_eq!(SpellId::get("fireball"), Some(id));
+        assert!(SpellId::contains("fireball"));
+    }
+
+    #[test]
+    fn test_does_not_intern_unknown_ids() {
+        assert_eq!(SpellId::get("never_created"), None);
+        assert!(!SpellId::contains("never_created"));
+        // Still unknown: the failed lookup did not insert it.
+        assert_eq!(SpellId::get("never_created"), None);
+    }
+
+    #[test]
+    fn test_each_type_has_its_own_interner() {
+        let _ = SpellId::new("shared_name");
+        assert!(!CommandId::contains("shared_name"));
+    }
+
+    #[test]
+    fn test_normalizes_before_lookup() {
+        let id = CommandId::new("teleport");
+        assert_eq!(CommandId::get("  TELEPORT "), Some(id));
+    }
+
+    #[test]
+    fn test_resolves_default_namespace() {
+        let id = ItemId::new("core:sword");
+        assert_eq!(ItemId::get("sword"), Some(id));
+        assert_eq!(ItemId::get("modded:sword"), None);
+        assert_eq!(ItemId::get("a:b:c"), None);
+    }
+}
+
+mod strict {
+    use super::*;
+
+    #[test]
+    fn test_try_from_looks_up() {
+        let id = CommandId::new("give");
+        assert_eq!(CommandId::try_from("give").unwrap(), id);
+        assert_eq!(CommandId::try_from(String::from("Give")).unwrap(), id);
+
+        let err = CommandId::try_from("take").unwrap_err();
+        assert!(matches!(err.kind(), IdErrorKind::Unknown));
+        assert_eq!(
+            err.to_string(),
+            r#"invalid CommandId "take": no such ID exists"#
+        );
+        assert!(!CommandId::contains("take"));
+    }
+
+    #[test]
+    fn test_from_str_looks_up() {
+        let id = CommandId::new("spawn");
+        assert_eq!("spawn".parse::<CommandId>().unwrap(), id);
+        assert!("despawn".parse::<CommandId>().is_err());
+        assert!(!CommandId::contains("despawn"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_deserialize_looks_up() {
+        let id = CommandId::new("kick");
+        assert_eq!(serde_json::from_str::<CommandId>(r#""kick""#).unwrap(), id);
+
+        let err = serde_json::from_str::<CommandId>(r#""ban""#).unwrap_err();
+        assert!(err.to_string().contains("no such ID exists"), "{err}");
+        assert!(!CommandId::contains("ban"));
+    }
+
+    #[test]
+    fn test_new_still_interns() {
+        assert!(!CommandId::contains("mute"));
+        let id = CommandId::new("mute");
+        assert!(CommandId::contains("mute"));
+        assert_eq!(CommandId::get("mute"), Some(id));
+    }
+}
@@ -0,0 +1,201 @@
+//! Tests for namespaced `namespace:path` IDs.
+
+use msg_interned_id::namespace::{self, NamespaceError};
+use msg_interned_id::{IdErrorKind, InternedId};
+
+/// Resource-location style IDs where bare paths belong to the base game.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    bevy_reflect = "bevy_reflect",
+    namespaced,
+    default_namespace = "core",
+    format = "snake_case",
+    skip(Default)
+)]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// A default of its own, since the empty string is not a valid namespaced ID.
+impl Default for SpellId {
+    fn default() -> Self {
+        Self::new("none")
+    }
+}
+
+/// Every ID must name its namespace explicitly.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, namespaced, skip(Default))]
+pub struct AssetId(msg_interned_id::intern::Interned<str>);
+
+/// The namespace error behind a rejected `try_new`.
+fn namespace_error<T: std::fmt::Debug>(
+    result: Result<T, msg_interned_id::IdError>,
+) -> NamespaceError {
+    match result.unwrap_err().kind() {
+        IdErrorKind::Namespace(err) => *err,
+        other => panic!("expected a namespace error, got {other:?}"),
+    }
+}
+
+mod accessors {
+    use super::*;
+
+    #[test]
+    fn test_splits_namespace_and_path() {
+        let id = SpellId::new("mymod:fireball");
+        assert_eq!(id.namespace(), "mymod");
+        assert_eq!(id.path(), "fireball");
+        assert_eq!(id.as_str(), "mymod:fireball");
+    }
+
+    #[test]
+    fn test_parts_point_into_the_interned_string() {
+        let id = AssetId::new("core:textures");
+        assert!(std::ptr::eq(id.namespace(), &id.as_str()[..4]));
+        assert!(std::ptr::eq(id.path(), &id.as_str()[5..]));
+    }
+
+    #[test]
+    fn test_same_path_in_different_namespaces_differs() {
+        assert_ne!(
+            SpellId::new("core:fireball"),
+            SpellId::new("mymod:fireball")
+        );
+    }
+}
+
+mod default_namespace {
+    use super::*;
+
+    #[test]
+    fn test_bare_path_resolves_to_default() {
+        let bare = SpellId::new("fireball");
+        assert_eq!(bare, SpellId::new("core:fireball"));
+        assert_eq!(bare.namespace(), "core");
+        assert!(std::ptr::eq(
+            bare.as_str(),
+            SpellId::new("core:fireball").as_str()
+        ));
+    }
+
+    #[test]
+    fn test_default_impl_uses_default_namespace() {
+        assert_eq!(SpellId::default().as_str(), "core:none");
+    }
+
+    #[test]
+    fn test_bare_path_without_default_is_rejected() {
+        assert_eq!(
+            namespace_error(AssetId::try_new("textures")),
+            NamespaceError::MissingNamespace
+        );
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn test_rejects_empty_parts() {
+        assert_eq!(
+            namespace_error(AssetId::try_new(":x")),
+            NamespaceError::EmptyNamespace
+        );
+        assert_eq!(
+            namespace_error(AssetId::try_new("x:")),
+            NamespaceError::EmptyPath
+        );
+        assert_eq!(
+            namespace_error(SpellId::try_new("")),
+            NamespaceError::EmptyPath
+        );
+    }
+
+    #[test]
+    fn test_rejects_extra_separator() {
+        assert_eq!(
+            namespace_error(AssetId::try_new("a:b:c")),
+            NamespaceError::ExtraSeparator(3)
+        );
+    }
+
+    #[test]
+    fn test_error_message() {
+        let err = AssetId::try_new("textures").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"invalid AssetId "textures": expected `namespace:path`"#
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "namespace must not be empty")]
+    fn test_new_panics() {
+        let _ = AssetId::new(":x");
+    }
+}
+
+mod format_rules {
+    use super::*;
+    use msg_interned_id::format::{CaseStyle, FormatRule};
+
+    #[test]
+    fn test_rules_apply_to_each_part() {
+        assert!(SpellId::try_new("my_mod:fire_ball").is_ok());
+
+        let err = SpellId::try_new("my_mod:fireBall").unwrap_err();
+        let IdErrorKind::Format(format) = err.kind() else {
+            panic!("expected a format error, got {:?}", err.kind());
+        };
+        assert_eq!(format.rule(), FormatRule::Case(CaseStyle::Snake));
+        assert_eq!(format.position(), 11);
+    }
+
+    #[test]
+    fn test_rules_see_the_resolved_id() {
+        let err = SpellId::try_new("Fireball").unwrap_err();
+        assert_eq!(err.value(), "core:Fireball");
+    }
+}
+
+mod conversions {
+    use super::*;
+
+    #[test]
+    fn test_display_and_from_str_round_trip() {
+        let id: SpellId = "fireball".parse().unwrap();
+        assert_eq!(id.to_string(), "core:fireball");
+        assert_eq!(id.to_string().parse::<SpellId>().unwrap(), id);
+        assert_eq!(SpellId::from("mymod:fireball").path(), "fireball");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trips_full_form() {
+        let id = SpellId::new("fireball");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#""core:fireball""#);
+        assert_eq!(serde_json::from_str::<SpellId>(&json).unwrap(), id);
+        assert_eq!(
+            serde_json::from_str::<SpellId>(r#""fireball""#).unwrap(),
+            id
+        );
+        assert!(serde_json::from_str::<AssetId>(r#""fireball""#).is_err());
+    }
+}
+
+mod runtime {
+    use super::*;
+
+    #[test]
+    fn test_resolve_borrows_full_ids() {
+        assert!(matches!(
+            namespace::resolve("a:b", Some("core")),
+            Ok(std::borrow::Cow::Borrowed("a:b"))
+        ));
+    }
+
+    #[test]
+    fn test_split_without_separator() {
+        assert_eq!(namespace::split("bare"), ("", "bare"));
+    }
+}
@@ -0,0 +1,132 @@
+//! Tests for input normalization (`normalize(trim, lowercase, nfc)`).
+
+use msg_interned_id::InternedId;
+use msg_interned_id::normalize::Normalization;
+use std::borrow::Cow;
+
+/// All three steps, as used for user-facing lookups.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, normalize(nfc, lowercase, trim))]
+pub struct TagId(msg_interned_id::intern::Interned<str>);
+
+/// Normalization runs before the format rules, so sloppy input still passes them.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    bevy_reflect = "bevy_reflect",
+    normalize(trim, lowercase),
+    format = "snake_case",
+    min_len = 1,
+    skip(Default)
+)]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Only trimming, so case and Unicode form are preserved.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, normalize(trim))]
+pub struct TrimmedId(msg_interned_id::intern::Interned<str>);
+
+mod whitespace {
+    use super::*;
+
+    #[test]
+    fn test_trimmed_spellings_share_one_string() {
+        let a = TrimmedId::new(" fireball ");
+        let b = TrimmedId::new("fireball");
+        assert_eq!(a, b);
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+    }
+
+    #[test]
+    fn test_trims_unicode_whitespace() {
+        assert_eq!(TrimmedId::new("\t\n x \r\n").as_str(), "x");
+        assert_eq!(TrimmedId::new("\u{3000}x\u{a0}").as_str(), "x");
+    }
+
+    #[test]
+    fn test_keeps_inner_whitespace_and_case() {
+        assert_eq!(TrimmedId::new(" Fire  Ball\n").as_str(), "Fire  Ball");
+        assert_eq!(TrimmedId::new("a\nb").as_str(), "a\nb");
+    }
+
+    #[test]
+    fn test_whitespace_only_becomes_empty() {
+        assert_eq!(TrimmedId::new(" \t "), TrimmedId::default());
+        assert!(SpellId::try_new("   ").is_err());
+    }
+}
+
+mod unicode {
+    use super::*;
+
+    const COMPOSED: &str = "caf\u{e9}";
+    const DECOMPOSED: &str = "cafe\u{301}";
+
+    #[test]
+    fn test_nfc_unifies_composed_and_decomposed() {
+        assert_eq!(TagId::new(COMPOSED), TagId::new(DECOMPOSED));
+        assert_eq!(TagId::new(DECOMPOSED).as_str(), COMPOSED);
+    }
+
+    #[test]
+    fn test_without_nfc_forms_stay_distinct() {
+        assert_ne!(TrimmedId::new(COMPOSED), TrimmedId::new(DECOMPOSED));
+    }
+
+    #[test]
+    fn test_lowercase_is_unicode_aware() {
+        assert_eq!(TagId::new(" CAF\u{c9} ").as_str(), COMPOSED);
+        assert_eq!(TagId::new("\u{130}").as_str(), "i\u{307}");
+        assert_eq!(TagId::new("ΣΟΦΙΑ").as_str(), "σοφια");
+    }
+
+    #[test]
+    fn test_uncased_text_is_unchanged() {
+        assert_eq!(TagId::new("火球").as_str(), "火球");
+    }
+}
+
+mod constructors {
+    use super::*;
+
+    #[test]
+    fn test_all_constructors_normalize() {
+        let expected = SpellId::new("fire_ball");
+        assert_eq!(SpellId::new(" Fire_Ball "), expected);
+        assert_eq!(SpellId::try_new("FIRE_BALL\n").unwrap(), expected);
+        assert_eq!(SpellId::from(" fire_ball"), expected);
+        assert_eq!(SpellId::from(String::from("Fire_ball ")), expected);
+        assert_eq!(" FIRE_BALL".parse::<SpellId>().unwrap(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_deserialize_normalizes() {
+        let id: SpellId = serde_json::from_str(r#"" Fire_Ball ""#).unwrap();
+        assert_eq!(id, SpellId::new("fire_ball"));
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""fire_ball""#);
+    }
+
+    #[test]
+    fn test_errors_report_the_normalized_value() {
+        let err = SpellId::try_new(" Fire Ball ").unwrap_err();
+        assert_eq!(err.value(), "fire ball");
+    }
+}
+
+mod runtime {
+    use super::*;
+
+    #[test]
+    fn test_borrows_when_unchanged() {
+        let all = Normalization::new().trim().lowercase().nfc();
+        assert!(matches!(all.apply("fire_ball"), Cow::Borrowed("fire_ball")));
+        assert!(matches!(all.apply("  fire "), Cow::Borrowed("fire")));
+        assert!(matches!(all.apply("Fire"), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_empty_normalization_is_identity() {
+        assert_eq!(Normalization::new().apply(" A\u{301} "), " A\u{301} ");
+        assert_eq!(Normalization::default(), Normalization::new());
+    }
+}
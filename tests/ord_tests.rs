@@ -0,0 +1,90 @@
+//! Tests for the opt-in `Ord` implementations generated by `ord = "..."`.
+
+use msg_interned_id::InternedId;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", ord = "lexical")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Only touched by `test_orders_by_creation`, so its indices are predictable.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, ord = "intern_order")]
+pub struct OrderedId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    standalone,
+    ord = "lexical",
+    namespaced,
+    default_namespace = "core",
+    skip(Default)
+)]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+mod lexical {
+    use super::*;
+
+    #[test]
+    fn test_orders_by_string_not_creation() {
+        let ice = SpellId::new("ord_ice_bolt");
+        let fire = SpellId::new("ord_fireball");
+        let arcane = SpellId::new("ord_arcane");
+
+        let mut spells = vec![ice, fire, arcane];
+        spells.sort();
+        assert_eq!(spells, [arcane, fire, ice]);
+    }
+
+    #[test]
+    fn test_btree_map_iterates_deterministically() {
+        let map: BTreeMap<SpellId, u32> = [("ord_b", 2), ("ord_c", 3), ("ord_a", 1)]
+            .into_iter()
+            .map(|(id, value)| (SpellId::new(id), value))
+            .collect();
+
+        let keys: Vec<&str> = map.keys().map(SpellId::as_str).collect();
+        assert_eq!(keys, ["ord_a", "ord_b", "ord_c"]);
+        assert_eq!(map[&SpellId::new("ord_b")], 2);
+    }
+
+    #[test]
+    fn test_consistent_with_eq() {
+        let a = SpellId::new("ord_same");
+        let b = SpellId::new("ord_same");
+        assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+        assert_eq!(a.partial_cmp(&b), Some(std::cmp::Ordering::Equal));
+        assert!(SpellId::new("ord_x") < SpellId::new("ord_y"));
+    }
+
+    #[test]
+    fn test_compares_full_namespaced_ids() {
+        let set: BTreeSet<ItemId> = ["sword", "mymod:axe", "core:axe"]
+            .into_iter()
+            .map(ItemId::new)
+            .collect();
+
+        let ids: Vec<&str> = set.iter().map(ItemId::as_str).collect();
+        assert_eq!(ids, ["core:axe", "core:sword", "mymod:axe"]);
+    }
+}
+
+mod intern_order {
+    use super::*;
+
+    #[test]
+    fn test_orders_by_creation() {
+        let zeta = OrderedId::new("zeta");
+        let alpha = OrderedId::new("alpha");
+        let mid = OrderedId::new("mid");
+
+        let mut ids = vec![mid, alpha, zeta];
+        ids.sort();
+        assert_eq!(ids, [zeta, alpha, mid]);
+        assert!(zeta < alpha);
+        assert_eq!(
+            OrderedId::new("alpha").cmp(&alpha),
+            std::cmp::Ordering::Equal
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Tests for `InternedIdPlugin` and `register_interned_id`.
+
+use std::any::TypeId;
+
+use bevy_app::App;
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{ReflectDeserialize, ReflectFromReflect, ReflectSerialize};
+use msg_interned_id::InternedId;
+use msg_interned_id::plugin::{InternedIdAppExt, InternedIdPlugin};
+use serde::de::DeserializeSeed;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    bevy_reflect = "bevy_reflect",
+    known(FIREBALL = "fireball", ICE_BOLT = "ice_bolt")
+)]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Only ever registered by `test_interns_known_ids`.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    bevy_reflect = "bevy_reflect",
+    known(SWORD = "sword", SHIELD = "shield")
+)]
+pub struct ItemId(bevy_ecs::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", skip(serde))]
+pub struct TagId(bevy_ecs::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    bevy_reflect = "bevy_reflect",
+    validate = not_reserved,
+    known(BROKEN = "reserved")
+)]
+pub struct BrokenId(bevy_ecs::intern::Interned<str>);
+
+/// Custom validation is the one rule the derive cannot check for `known(...)` values.
+fn not_reserved(id: &str) -> Result<(), &'static str> {
+    if id == "reserved" {
+        Err("is reserved")
+    } else {
+        Ok(())
+    }
+}
+
+/// A headless app with `Id` registered through the plugin.
+fn app_with<Id: InternedId + bevy_reflect::GetTypeRegistration>() -> App {
+    let mut app = App::new();
+    app.add_plugins(InternedIdPlugin::<Id>::default());
+    app
+}
+
+mod registration {
+    use super::*;
+
+    #[test]
+    fn test_registers_type_data() {
+        let app = app_with::<SpellId>();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let registration = registry.get(TypeId::of::<SpellId>()).unwrap();
+
+        assert!(registration.data::<ReflectFromReflect>().is_some());
+        assert!(registration.data::<ReflectDefault>().is_some());
+        assert!(registration.data::<ReflectSerialize>().is_some());
+        assert!(registration.data::<ReflectDeserialize>().is_some());
+    }
+
+    #[test]
+    fn test_skipped_serde_has_no_serde_type_data() {
+        let mut app = App::new();
+        app.register_interned_id::<TagId>();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let registration = registry.get(TypeId::of::<TagId>()).unwrap();
+
+        assert!(registration.data::<ReflectFromReflect>().is_some());
+        assert!(registration.data::<ReflectSerialize>().is_none());
+        assert!(registration.data::<ReflectDeserialize>().is_none());
+    }
+
+    #[test]
+    fn test_extension_is_idempotent() {
+        let mut app = app_with::<SpellId>();
+        app.register_interned_id::<SpellId>()
+            .register_interned_id::<SpellId>();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        assert!(registry.contains(TypeId::of::<SpellId>()));
+    }
+}
+
+mod known_ids {
+    use super::*;
+
+    #[test]
+    fn test_interns_known_ids() {
+        assert_eq!(ItemId::count(), 0);
+        let _app = app_with::<ItemId>();
+
+        assert_eq!(ItemId::count(), 2);
+        let ids: Vec<&str> = ItemId::all().map(|id| id.as_str()).collect();
+        assert_eq!(ids, ["sword", "shield"]);
+        assert_eq!(ItemId::from_index(0), Some(ItemId::SWORD.get()));
+    }
+
+    #[test]
+    #[should_panic(expected = "is reserved")]
+    fn test_invalid_known_id_fails_at_startup() {
+        let _app = app_with::<BrokenId>();
+    }
+}
+
+mod scenes {
+    use super::*;
+
+    #[test]
+    fn test_reflected_serde_round_trip() {
+        let app = app_with::<SpellId>();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+
+        let id = SpellId::new("scene_fireball");
+        let json = serde_json::to_string(&TypedReflectSerializer::new(&id, &registry)).unwrap();
+        assert_eq!(json, r#""scene_fireball""#);
+
+        let registration = registry.get(TypeId::of::<SpellId>()).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(value.try_downcast_ref::<SpellId>(), Some(&id));
+    }
+}
@@ -0,0 +1,277 @@
+//! Tests for the `IdRegistry` resource.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::ReflectResource;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy_reflect::{GetTypeRegistration, PartialReflect, Reflect, ReflectRef, TypeRegistry};
+use msg_interned_id::InternedId;
+use msg_interned_id::registry::IdRegistry;
+use serde::de::DeserializeSeed;
+use serde::{Deserialize, Serialize};
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Only ever holds the IDs created by `test_iterates_in_index_order`.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct OrderedId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, strict(serde), known(FIREBALL = "fireball"))]
+pub struct RuneId(msg_interned_id::intern::Interned<str>);
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+struct SpellData {
+    damage: u32,
+    mana_cost: u32,
+}
+
+fn spell(damage: u32) -> SpellData {
+    SpellData {
+        damage,
+        mana_cost: 10,
+    }
+}
+
+mod definitions {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let fireball = SpellId::new("reg_fireball");
+        let ice = SpellId::new("reg_ice");
+        let mut spells = IdRegistry::new();
+
+        assert!(spells.is_empty());
+        spells.insert(fireball, spell(50)).unwrap().mana_cost = 20;
+        assert_eq!(
+            spells.get(fireball),
+            Some(&SpellData {
+                damage: 50,
+                mana_cost: 20
+            })
+        );
+        assert_eq!(spells.get(ice), None);
+        assert!(spells.contains(fireball));
+        assert_eq!(spells.len(), 1);
+
+        spells.get_mut(fireball).unwrap().damage += 5;
+        assert_eq!(spells[fireball].damage, 55);
+
+        assert_eq!(spells.remove(fireball).map(|data| data.damage), Some(55));
+        assert_eq!(spells.remove(fireball), None);
+        assert!(spells.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_duplicate_definitions() {
+        let fireball = SpellId::new("reg_dup_fireball");
+        let mut spells = IdRegistry::new();
+        spells.insert(fireball, spell(50)).unwrap();
+
+        let err = spells.insert(fireball, spell(10)).unwrap_err();
+        assert_eq!(err.id(), fireball);
+        assert_eq!(
+            err.to_string(),
+            r#"SpellId "reg_dup_fireball" is already defined"#
+        );
+        assert_eq!(spells[fireball].damage, 50);
+    }
+
+    #[test]
+    fn test_replace_overwrites() {
+        let fireball = SpellId::new("reg_replace_fireball");
+        let mut spells = IdRegistry::new();
+        assert_eq!(spells.replace(fireball, spell(50)), None);
+        assert_eq!(spells.replace(fireball, spell(60)), Some(spell(50)));
+        assert_eq!(spells[fireball].damage, 60);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"SpellId "reg_missing" is not defined"#)]
+    fn test_index_panics_for_undefined_ids() {
+        let spells: IdRegistry<SpellId, SpellData> = IdRegistry::new();
+        let _ = &spells[SpellId::new("reg_missing")];
+    }
+
+    #[test]
+    fn test_iterates_in_index_order() {
+        let [c, a, b] = ["c", "a", "b"].map(OrderedId::new);
+        let mut registry = IdRegistry::new();
+        for (id, value) in [(b, 2), (a, 1), (c, 3)] {
+            registry.insert(id, value).unwrap();
+        }
+
+        assert_eq!(
+            registry.iter().collect::<Vec<_>>(),
+            [(c, &3), (a, &1), (b, &2)]
+        );
+        assert_eq!(registry.ids().collect::<Vec<_>>(), [c, a, b]);
+        assert_eq!((&registry).into_iter().len(), 3);
+    }
+}
+
+mod merge {
+    use super::*;
+
+    #[test]
+    fn test_moves_definitions() {
+        let [fireball, ice] = ["reg_merge_fireball", "reg_merge_ice"].map(SpellId::new);
+        let mut base = IdRegistry::new();
+        base.insert(fireball, spell(50)).unwrap();
+        let mut expansion = IdRegistry::new();
+        expansion.insert(ice, spell(35)).unwrap();
+
+        base.merge(expansion).unwrap();
+        assert_eq!(base.len(), 2);
+        assert_eq!(base[ice].damage, 35);
+    }
+
+    #[test]
+    fn test_rejects_overlap_without_changes() {
+        let [fireball, ice] = ["reg_overlap_fireball", "reg_overlap_ice"].map(SpellId::new);
+        let mut base = IdRegistry::new();
+        base.insert(fireball, spell(50)).unwrap();
+        let mut expansion = IdRegistry::new();
+        expansion.insert(ice, spell(35)).unwrap();
+        expansion.insert(fireball, spell(99)).unwrap();
+
+        let err = base.merge(expansion).unwrap_err();
+        assert_eq!(err.id(), fireball);
+        assert_eq!(base.len(), 1);
+        assert_eq!(base[fireball].damage, 50);
+    }
+}
+
+mod serde_integration {
+    use super::*;
+
+    #[test]
+    fn test_loads_map_keyed_by_id_strings() {
+        let spells: IdRegistry<SpellId, SpellData> = serde_json::from_str(
+            r#"{
+                "reg_json_fireball": {"damage": 50, "mana_cost": 20},
+                "reg_json_ice": {"damage": 35, "mana_cost": 15}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(spells.len(), 2);
+        assert_eq!(spells[SpellId::new("reg_json_ice")].mana_cost, 15);
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let mut spells = IdRegistry::new();
+        spells
+            .insert(SpellId::new("reg_rt_fireball"), spell(50))
+            .unwrap();
+        spells
+            .insert(SpellId::new("reg_rt_ice"), spell(35))
+            .unwrap();
+
+        let json = serde_json::to_string(&spells).unwrap();
+        assert_eq!(
+            json,
+            r#"{"reg_rt_fireball":{"damage":50,"mana_cost":10},"reg_rt_ice":{"damage":35,"mana_cost":10}}"#
+        );
+        let back: IdRegistry<SpellId, SpellData> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, spells);
+    }
+
+    #[test]
+    fn test_rejects_duplicate_keys() {
+        let err = serde_json::from_str::<IdRegistry<SpellId, u32>>(
+            r#"{"reg_json_dup": 1, "reg_json_dup": 2}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"SpellId "reg_json_dup" is already defined at line 1 column 38"#
+        );
+    }
+
+    #[test]
+    fn test_keys_follow_the_id_type() {
+        let spells: IdRegistry<RuneId, u32> = serde_json::from_str(r#"{"fireball": 1}"#).unwrap();
+        assert_eq!(spells[RuneId::FIREBALL.get()], 1);
+
+        let err = serde_json::from_str::<IdRegistry<RuneId, u32>>(r#"{"firebal": 1}"#).unwrap_err();
+        assert!(
+            err.to_string().contains(r#"did you mean "fireball"?"#),
+            "{err}"
+        );
+    }
+}
+
+mod resource {
+    use super::*;
+
+    #[test]
+    fn test_used_as_a_resource() {
+        let fireball = SpellId::new("reg_res_fireball");
+        let mut spells = IdRegistry::new();
+        spells.insert(fireball, spell(50)).unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(spells);
+        world.resource_mut::<IdRegistry<SpellId, SpellData>>()[fireball].damage = 7;
+        let damage = world
+            .run_system_once(|spells: Res<IdRegistry<SpellId, SpellData>>| {
+                spells.iter().map(|(_, data)| data.damage).sum::<u32>()
+            })
+            .unwrap();
+        assert_eq!(damage, 7);
+    }
+
+    #[test]
+    fn test_reflected_as_a_resource() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<IdRegistry<SpellId, SpellData>>();
+        let registration = registry
+            .get(std::any::TypeId::of::<IdRegistry<SpellId, SpellData>>())
+            .unwrap();
+        assert!(registration.data::<ReflectResource>().is_some());
+        let _ = IdRegistry::<SpellId, SpellData>::get_type_registration();
+
+        let mut spells = IdRegistry::new();
+        spells
+            .insert(SpellId::new("reg_reflect_ice"), spell(35))
+            .unwrap();
+        assert!(matches!(spells.reflect_ref(), ReflectRef::Opaque(_)));
+        let clone = spells.reflect_clone().unwrap();
+        let clone = clone
+            .downcast_ref::<IdRegistry<SpellId, SpellData>>()
+            .unwrap();
+        assert_eq!(*clone, spells);
+    }
+
+    #[test]
+    fn test_reflected_serde_rejects_duplicate_keys() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<IdRegistry<SpellId, SpellData>>();
+        let registration = registry
+            .get(std::any::TypeId::of::<IdRegistry<SpellId, SpellData>>())
+            .unwrap();
+
+        let mut spells = IdRegistry::new();
+        spells
+            .insert(SpellId::new("reg_reflect_frost"), spell(20))
+            .unwrap();
+        let json = serde_json::to_string(&TypedReflectSerializer::new(&spells, &registry)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"reg_reflect_frost":{"damage":20,"mana_cost":10}}"#
+        );
+
+        let twice = r#"{"reg_reflect_frost":{"damage":1,"mana_cost":1},"reg_reflect_frost":{"damage":2,"mana_cost":2}}"#;
+        let mut deserializer = serde_json::Deserializer::from_str(twice);
+        let err = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap_err();
+        assert!(err.to_string().contains("already defined"), "{err}");
+    }
+}
@@ -0,0 +1,140 @@
+//! Tests for the compact `serde_repr = "stable_hash"` and `serde_repr = "index"` representations.
+
+use msg_interned_id::InternedId;
+use msg_interned_id::hash::stable_hash;
+use serde::{Deserialize, Serialize};
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", serde_repr = "stable_hash")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, serde_repr = "index")]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, serde_repr = "string")]
+pub struct EffectId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    standalone,
+    serde = "::serde",
+    serde_repr = "stable_hash",
+    format = "snake_case"
+)]
+pub struct QuestId(msg_interned_id::intern::Interned<str>);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Snapshot {
+    spell: SpellId,
+    items: Vec<ItemId>,
+}
+
+mod stable_hash_repr {
+    use super::*;
+
+    #[test]
+    fn test_binary_stores_the_hash() {
+        let id = SpellId::new("repr_fireball");
+        let bytes = bincode::serialize(&id).unwrap();
+
+        assert_eq!(bytes, stable_hash("repr_fireball").to_le_bytes());
+        assert_eq!(bincode::deserialize::<SpellId>(&bytes).unwrap(), id);
+    }
+
+    #[test]
+    fn test_human_readable_keeps_the_string() {
+        let id = SpellId::new("repr_ice_bolt");
+        let json = serde_json::to_string(&id).unwrap();
+
+        assert_eq!(json, r#""repr_ice_bolt""#);
+        assert_eq!(serde_json::from_str::<SpellId>(&json).unwrap(), id);
+    }
+
+    #[test]
+    fn test_unknown_hash_is_an_error() {
+        let bytes = bincode::serialize(&stable_hash("repr_never_created")).unwrap();
+        let err = bincode::deserialize::<SpellId>(&bytes).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "no SpellId with stable hash {:#018x} exists",
+                stable_hash("repr_never_created")
+            )
+        );
+    }
+
+    #[test]
+    fn test_validation_still_applies_to_strings() {
+        assert!(serde_json::from_str::<QuestId>(r#""Not Snake""#).is_err());
+
+        let id = QuestId::new("repr_rescue");
+        let bytes = bincode::serialize(&id).unwrap();
+        assert_eq!(bincode::deserialize::<QuestId>(&bytes).unwrap(), id);
+    }
+
+    #[test]
+    fn test_from_stable_hash() {
+        let id = SpellId::new("repr_lookup");
+        assert_eq!(SpellId::from_stable_hash(id.stable_hash()), Some(id));
+        assert_eq!(SpellId::from_stable_hash(stable_hash("repr_missing")), None);
+        assert!(!SpellId::contains("repr_missing"));
+    }
+}
+
+mod index_repr {
+    use super::*;
+
+    #[test]
+    fn test_binary_stores_the_index() {
+        let id = ItemId::new("repr_sword");
+        let bytes = bincode::serialize(&id).unwrap();
+
+        assert_eq!(bytes, id.index().to_le_bytes());
+        assert_eq!(bincode::deserialize::<ItemId>(&bytes).unwrap(), id);
+    }
+
+    #[test]
+    fn test_unknown_index_is_an_error() {
+        let bytes = bincode::serialize(&u32::MAX).unwrap();
+        let err = bincode::deserialize::<ItemId>(&bytes).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("no ItemId with index {} exists", u32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let snapshot = Snapshot {
+            spell: SpellId::new("repr_snapshot"),
+            items: vec![ItemId::new("repr_shield"), ItemId::new("repr_potion")],
+        };
+
+        let bytes = bincode::serialize(&snapshot).unwrap();
+        assert_eq!(bytes.len(), 8 + 8 + 2 * 4); // hash, vec length, two indices
+        assert_eq!(bincode::deserialize::<Snapshot>(&bytes).unwrap(), snapshot);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(
+            json,
+            r#"{"spell":"repr_snapshot","items":["repr_shield","repr_potion"]}"#
+        );
+    }
+}
+
+mod string_repr {
+    use super::*;
+
+    #[test]
+    fn test_binary_stores_the_string() {
+        let id = EffectId::new("repr_burning");
+        let bytes = bincode::serialize(&id).unwrap();
+
+        assert_eq!(bytes, bincode::serialize("repr_burning").unwrap());
+        assert_eq!(bincode::deserialize::<EffectId>(&bytes).unwrap(), id);
+    }
+}
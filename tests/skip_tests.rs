@@ -0,0 +1,177 @@
+//! Tests for `#[interned_id(skip(...))]`, showing that user-written replacements compile.
+
+#[cfg(feature = "reflect")]
+use bevy_reflect::std_traits::ReflectDefault;
+#[cfg(feature = "reflect")]
+use bevy_reflect::{GetTypeRegistration, PartialReflect, Reflect, TypePath, TypeRegistry};
+use msg_interned_id::InternedId;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// ID with a validating `Deserialize` in place of the generated serde impls.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", skip(serde))]
+pub struct CheckedId(bevy_ecs::intern::Interned<str>);
+
+impl Serialize for CheckedId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CheckedId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if s.is_empty() {
+            return Err(D::Error::custom("CheckedId must not be empty"));
+        }
+        Ok(Self::new(&s))
+    }
+}
+
+/// ID with its own `Default`, registered as `ReflectDefault` by hand.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", skip(Default))]
+pub struct FallbackId(bevy_ecs::intern::Interned<str>);
+
+impl Default for FallbackId {
+    fn default() -> Self {
+        Self::new("unknown")
+    }
+}
+
+/// Reflected ID without any `Default`, which must still compile.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", skip(Default))]
+pub struct NoDefaultId(bevy_ecs::intern::Interned<str>);
+
+/// ID that must not coerce into `&str` APIs.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", skip(Deref))]
+pub struct OpaqueId(bevy_ecs::intern::Interned<str>);
+
+/// ID with hand-written `Display` and `From` impls.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", skip(Display, From))]
+pub struct TagId(bevy_ecs::intern::Interned<str>);
+
+impl fmt::Display for TagId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.as_str())
+    }
+}
+
+impl From<&str> for TagId {
+    fn from(s: &str) -> Self {
+        Self::new(&s.to_lowercase())
+    }
+}
+
+/// ID whose reflection comes from `#[derive(Reflect)]` instead of the generated impls.
+#[cfg(feature = "reflect")]
+#[derive(InternedId, Reflect, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(skip(reflect))]
+#[reflect(opaque, Clone, Debug, Hash, PartialEq)]
+pub struct DerivedReflectId(bevy_ecs::intern::Interned<str>);
+
+mod skip_serde {
+    use super::*;
+
+    #[test]
+    fn test_custom_deserialize_validates() {
+        let id: CheckedId = serde_json::from_str("\"valid\"").unwrap();
+        assert_eq!(id.as_str(), "valid");
+
+        let err = serde_json::from_str::<CheckedId>("\"\"").unwrap_err();
+        assert!(err.to_string().contains("must not be empty"));
+    }
+
+    #[test]
+    fn test_custom_serialize() {
+        let json = serde_json::to_string(&CheckedId::new("out")).unwrap();
+        assert_eq!(json, "\"out\"");
+    }
+}
+
+mod skip_default {
+    use super::*;
+
+    #[test]
+    fn test_custom_default() {
+        assert_eq!(FallbackId::default().as_str(), "unknown");
+    }
+
+    #[test]
+    #[cfg(feature = "reflect")]
+    fn test_reflect_default_not_registered() {
+        let registration = NoDefaultId::get_type_registration();
+        assert!(registration.data::<ReflectDefault>().is_none());
+        assert_eq!(NoDefaultId::new("plain").as_str(), "plain");
+    }
+
+    #[test]
+    #[cfg(feature = "reflect")]
+    fn test_custom_default_registered_by_hand() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<FallbackId>();
+        assert!(
+            registry
+                .get_type_data::<ReflectDefault>(std::any::TypeId::of::<FallbackId>())
+                .is_none()
+        );
+
+        registry.register_type_data::<FallbackId, ReflectDefault>();
+        let reflect_default = registry
+            .get_type_data::<ReflectDefault>(std::any::TypeId::of::<FallbackId>())
+            .unwrap();
+        let value = reflect_default.default();
+        assert_eq!(
+            value.downcast_ref::<FallbackId>().unwrap().as_str(),
+            "unknown"
+        );
+    }
+}
+
+mod skip_deref {
+    use super::*;
+
+    #[test]
+    fn test_still_has_as_str_and_display() {
+        let id = OpaqueId::new("opaque");
+        assert_eq!(id.as_str(), "opaque");
+        assert_eq!(id.to_string(), "opaque");
+    }
+}
+
+mod skip_display_and_from {
+    use super::*;
+
+    #[test]
+    fn test_custom_display() {
+        assert_eq!(TagId::new("rare").to_string(), "#rare");
+    }
+
+    #[test]
+    fn test_custom_from() {
+        let id: TagId = "Rare".into();
+        assert_eq!(id, TagId::new("rare"));
+    }
+}
+
+#[cfg(feature = "reflect")]
+mod skip_reflect {
+    use super::*;
+
+    #[test]
+    fn test_derived_reflection() {
+        let id = DerivedReflectId::new("derived");
+        assert_eq!(DerivedReflectId::short_type_path(), "DerivedReflectId");
+        assert_eq!(
+            id.reflect_partial_eq(&DerivedReflectId::new("derived")),
+            Some(true)
+        );
+        let cloned = id.reflect_clone().unwrap();
+        assert_eq!(cloned.downcast_ref::<DerivedReflectId>(), Some(&id));
+    }
+}
@@ -0,0 +1,78 @@
+//! Tests for the content-based `stable_hash()`.
+
+#[cfg(feature = "reflect")]
+use bevy_reflect::PartialReflect;
+use msg_interned_id::InternedId;
+use msg_interned_id::hash::stable_hash;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct EffectId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, namespaced, default_namespace = "core", skip(Default))]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+mod algorithm {
+    use super::*;
+
+    #[test]
+    fn test_matches_fnv1a_reference_values() {
+        assert_eq!(stable_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(stable_hash("foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_usable_in_const() {
+        const FIREBALL: u64 = stable_hash("fireball");
+        assert_eq!(FIREBALL, 0x3f36_caf6_1d84_3bf8);
+    }
+}
+
+mod ids {
+    use super::*;
+
+    #[test]
+    fn test_depends_only_on_content() {
+        let spell = SpellId::new("fireball");
+        let effect = EffectId::new("fireball");
+
+        assert_eq!(spell.stable_hash(), 0x3f36_caf6_1d84_3bf8);
+        assert_eq!(spell.stable_hash(), effect.stable_hash());
+        assert_eq!(
+            spell.stable_hash(),
+            SpellId::from_static("fireball").stable_hash()
+        );
+        assert_ne!(spell.stable_hash(), SpellId::new("ice_bolt").stable_hash());
+    }
+
+    #[test]
+    fn test_hashes_the_stored_string() {
+        // Bare paths get their default namespace before they are hashed.
+        let sword = ItemId::new("sword");
+        assert_eq!(sword.stable_hash(), stable_hash("core:sword"));
+        assert_eq!(sword.stable_hash(), 0x12a6_89aa_fb50_13d5);
+    }
+
+    #[test]
+    fn test_available_through_the_trait() {
+        fn hash_of<Id: InternedId>(id: Id) -> u64 {
+            id.stable_hash()
+        }
+
+        let id = EffectId::new("stable_burning");
+        assert_eq!(hash_of(id), stable_hash("stable_burning"));
+    }
+
+    #[test]
+    #[cfg(feature = "reflect")]
+    fn test_reflect_hash_is_stable() {
+        let id = SpellId::new("stable_reflected");
+        assert_eq!(id.reflect_hash(), Some(stable_hash("stable_reflected")));
+    }
+}
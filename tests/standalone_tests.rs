@@ -0,0 +1,129 @@
+//! Tests for standalone ID types, which use the crate's own interner instead of Bevy's.
+
+use msg_interned_id::InternedId;
+use msg_interned_id::intern::{Interned, Interner};
+
+/// Standalone ID type, as a Bevy-free crate would declare it.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone)]
+pub struct StandaloneId(msg_interned_id::intern::Interned<str>);
+
+/// Standalone ID using an imported `Interned` and an explicit runtime crate path.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, crate = "msg_interned_id")]
+pub struct ImportedId(Interned<str>);
+
+/// The same field type used by a Bevy-enabled ID, which keeps its reflection impls.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SharedId(msg_interned_id::intern::Interned<str>);
+
+mod core_functionality {
+    use super::*;
+
+    #[test]
+    fn test_new_and_as_str() {
+        let id = StandaloneId::new("standalone");
+        assert_eq!(id.as_str(), "standalone");
+    }
+
+    #[test]
+    fn test_interning_deduplicates() {
+        let id1 = StandaloneId::new("same_value");
+        let id2 = StandaloneId::new("same_value");
+        assert_eq!(id1, id2);
+        assert!(std::ptr::eq(id1.as_str(), id2.as_str()));
+    }
+
+    #[test]
+    fn test_different_values_are_different() {
+        assert_ne!(StandaloneId::new("value_a"), StandaloneId::new("value_b"));
+    }
+
+    #[test]
+    fn test_separate_interners_for_types() {
+        let standalone = StandaloneId::new("shared_name");
+        let imported = ImportedId::new("shared_name");
+        assert_eq!(standalone.as_str(), imported.as_str());
+        assert!(!std::ptr::eq(standalone.as_str(), imported.as_str()));
+    }
+
+    #[test]
+    fn test_standard_traits() {
+        let id: StandaloneId = "from_str".into();
+        assert_eq!(format!("{id}"), "from_str");
+        assert!(id.starts_with("from"));
+        assert_eq!(StandaloneId::default().as_str(), "");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_roundtrip() {
+        let id = StandaloneId::new("serde_value");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "\"serde_value\"");
+        let back: StandaloneId = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, id);
+    }
+}
+
+#[cfg(feature = "reflect")]
+mod shared_field_type {
+    use super::*;
+    use bevy_reflect::{FromReflect, PartialReflect, TypePath};
+
+    #[test]
+    fn test_bevy_mode_accepts_standalone_field() {
+        let id = SharedId::new("shared");
+        assert_eq!(id, SharedId::new("shared"));
+        assert_eq!(SharedId::short_type_path(), "SharedId");
+        assert_eq!(SharedId::from_reflect(id.as_partial_reflect()), Some(id));
+    }
+}
+
+mod interner {
+    use super::*;
+
+    #[test]
+    fn test_interner_deduplicates() {
+        static INTERNER: Interner = Interner::new();
+        let a = INTERNER.intern("value");
+        let b = INTERNER.intern(&String::from("value"));
+        assert_eq!(a, b);
+        assert_eq!(&*a, "value");
+    }
+
+    #[test]
+    fn test_interned_compares_by_pointer() {
+        static FIRST: &str = "same";
+        let copy: &'static str = Box::leak(Box::from(FIRST));
+        assert_eq!(Interned(FIRST), Interned(FIRST));
+        assert_ne!(Interned(FIRST), Interned(copy));
+    }
+}
+
+mod thread_safety {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[test]
+    fn test_same_string_concurrent() {
+        let barrier = Arc::new(Barrier::new(10));
+
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    StandaloneId::new("concurrent_test")
+                })
+            })
+            .collect();
+
+        let ids: Vec<StandaloneId> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        for id in &ids {
+            assert!(std::ptr::eq(id.as_str(), ids[0].as_str()));
+        }
+    }
+}
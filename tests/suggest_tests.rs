@@ -0,0 +1,194 @@
+//! Tests for "did you mean" suggestions and `strict(serde)` deserialization.
+
+use msg_interned_id::suggest::{closest, edit_distance};
+use msg_interned_id::{IdError, IdErrorKind, InternedId};
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+/// Data files may only name existing or declared spells; code still interns freely.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    standalone,
+    strict(serde),
+    known(FIREBALL = "fireball", FIREWALL = "firewall")
+)]
+pub struct RuneId(msg_interned_id::intern::Interned<str>);
+
+/// Suggestions see through normalization and the default namespace.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(
+    standalone,
+    strict,
+    namespaced,
+    default_namespace = "core",
+    normalize(lowercase),
+    skip(Default)
+)]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+mod distance {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("fireball", "fireball"), 0);
+        assert_eq!(edit_distance("firebal", "fireball"), 1);
+        assert_eq!(edit_distance("fierball", "fireball"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_edit_distance_counts_chars() {
+        assert_eq!(edit_distance("épée", "epee"), 2);
+        assert_eq!(edit_distance("火球", "火"), 1);
+    }
+}
+
+mod suggest {
+    use super::*;
+
+    #[test]
+    fn test_ranks_nearest_first() {
+        let fireball = SpellId::new("sg_fireball");
+        let firewall = SpellId::new("sg_firewall");
+        let _ = SpellId::new("sg_frostbolt");
+        assert_eq!(SpellId::suggest("sg_firebal", 3), [fireball, firewall]);
+        assert_eq!(SpellId::suggest("sg_firebal", 1), [fireball]);
+    }
+
+    #[test]
+    fn test_breaks_ties_lexically() {
+        let a = SpellId::new("sg_tie_a");
+        let b = SpellId::new("sg_tie_b");
+        assert_eq!(SpellId::suggest("sg_tie_c", 2), [a, b]);
+    }
+
+    #[test]
+    fn test_leaves_out_distant_ids() {
+        let _ = SpellId::new("sg_meteor");
+        assert!(SpellId::suggest("sg_heal_over_time", 5).is_empty());
+        assert!(SpellId::suggest("sg_meteor", 0).is_empty());
+    }
+
+    #[test]
+    fn test_does_not_intern_the_query() {
+        let _ = SpellId::suggest("sg_never_created", 3);
+        assert!(!SpellId::contains("sg_never_created"));
+    }
+
+    #[test]
+    fn test_includes_declared_ids() {
+        assert_eq!(
+            RuneId::suggest("firebal", 3),
+            [RuneId::FIREBALL.get(), RuneId::FIREWALL.get()]
+        );
+    }
+
+    #[test]
+    fn test_compares_prepared_query() {
+        let sword = ItemId::new("core:sword");
+        assert_eq!(ItemId::suggest("SWORDS", 3), [sword]);
+        // Queries that fail validation are compared as written.
+        assert_eq!(ItemId::suggest("core:swor:d", 3), [sword]);
+    }
+
+    #[test]
+    fn test_closest_reports_repeats_once() {
+        let id = SpellId::new("sg_repeat");
+        assert_eq!(closest("sg_repeet", [id, id, id], 3), [id]);
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn test_lists_suggestions() {
+        let err = IdError::unknown("SpellId", "firebal");
+        assert_eq!(
+            err.to_string(),
+            r#"invalid SpellId "firebal": no such ID exists"#
+        );
+
+        let err = err.with_suggestions(["fireball"]);
+        assert_eq!(
+            err.to_string(),
+            r#"invalid SpellId "firebal": no such ID exists; did you mean "fireball"?"#
+        );
+
+        let err = err.with_suggestions(["firewall", "fireballs"]);
+        assert_eq!(err.suggestions(), ["fireball", "firewall", "fireballs"]);
+        assert_eq!(
+            err.to_string(),
+            r#"invalid SpellId "firebal": no such ID exists; did you mean "fireball", "firewall" or "fireballs"?"#
+        );
+    }
+
+    #[test]
+    fn test_strict_conversions_do_not_suggest() {
+        let _ = ItemId::new("core:shield");
+        let err = ItemId::try_from("shiel").unwrap_err();
+        assert!(matches!(err.kind(), IdErrorKind::Unknown));
+        assert!(err.suggestions().is_empty());
+    }
+
+    #[test]
+    fn test_parse_suggests() {
+        let err = <RuneId as InternedId>::parse("firewal").unwrap_err();
+        assert!(matches!(err.kind(), IdErrorKind::Unknown));
+        assert_eq!(err.suggestions(), ["firewall", "fireball"]);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod strict_serde {
+    use super::*;
+
+    #[test]
+    fn test_accepts_existing_ids() {
+        let id = RuneId::new("earthquake");
+        assert_eq!(
+            serde_json::from_str::<RuneId>(r#""earthquake""#).unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn test_accepts_declared_ids() {
+        let id: RuneId = serde_json::from_str(r#""firewall""#).unwrap();
+        assert_eq!(id, RuneId::FIREWALL.get());
+    }
+
+    #[test]
+    fn test_rejects_unknown_ids_with_suggestions() {
+        let err = serde_json::from_str::<RuneId>(r#""firebal""#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"invalid RuneId "firebal": no such ID exists; did you mean "fireball" or "firewall"? at line 1 column 9"#
+        );
+        assert!(!RuneId::contains("firebal"));
+    }
+
+    #[test]
+    fn test_rejects_unknown_ids_in_strict_types() {
+        let _ = ItemId::new("core:helmet");
+        let err = serde_json::from_str::<ItemId>(r#""Helmat""#).unwrap_err();
+        assert!(
+            err.to_string().contains(r#"did you mean "core:helmet"?"#),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_conversions_still_intern() {
+        assert!(!RuneId::contains("tsunami"));
+        let id = RuneId::from("tsunami");
+        assert_eq!("tsunami".parse::<RuneId>().unwrap(), id);
+        assert!(serde_json::from_str::<RuneId>(r#""tsunami""#).is_ok());
+    }
+}
@@ -0,0 +1,7 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+#[interned_id]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+fn main() {}
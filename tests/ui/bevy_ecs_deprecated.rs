@@ -0,0 +1,9 @@
+#![deny(deprecated)]
+
+use msg_interned_id::InternedId;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_ecs = "bevy_ecs", skip(reflect))]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+fn main() {}
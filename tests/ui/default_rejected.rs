@@ -0,0 +1,15 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+#[interned_id(standalone, format = "snake_case", min_len = 1)]
+pub struct SpellId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId)]
+#[interned_id(standalone, namespaced)]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId)]
+#[interned_id(standalone, hierarchical(separator = "/"))]
+pub struct ConfigKey(msg_interned_id::intern::Interned<str>);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+#[interned_id(bevy_reflect = "bevy_reflect")]
+#[interned_id(bevy_reflect = "bevy::reflect")]
+pub struct SpellId(bevy_ecs::intern::Interned<str>);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+pub enum SpellId {
+    Fireball,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+pub struct SpellId(bevy_ecs::intern::Interned<str>, u32);
+
+fn main() {}
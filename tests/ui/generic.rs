@@ -0,0 +1,6 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+pub struct SpellId<T>(bevy_ecs::intern::Interned<str>, std::marker::PhantomData<T>);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+#[interned_id(standalone, hierarchical(separator = "ab"))]
+pub struct SpellId(msg_interned_id::intern::Interned<str>);
+
+fn main() {}
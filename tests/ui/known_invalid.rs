@@ -0,0 +1,7 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+#[interned_id(standalone, known(spells::FIREBALL = "fireball", ICE_BOLT = 3))]
+pub struct SpellId(msg_interned_id::intern::Interned<str>);
+
+fn main() {}
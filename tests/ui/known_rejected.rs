@@ -0,0 +1,32 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+#[interned_id(
+    standalone,
+    normalize(lowercase),
+    format = "snake_case",
+    max_len = 12,
+    known(HEAL = "Heal", BROKEN = "not snake", LONG = "much_too_long")
+)]
+pub struct AbilityId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId)]
+#[interned_id(
+    standalone,
+    namespaced,
+    known(SWORD = "sword", AXE = "core::axe"),
+    skip(Default)
+)]
+pub struct ItemId(msg_interned_id::intern::Interned<str>);
+
+#[derive(InternedId)]
+#[interned_id(
+    standalone,
+    hierarchical,
+    format = "snake_case",
+    known(SPEED = "buff..speed", SLOW = "debuff.Slow"),
+    skip(Default)
+)]
+pub struct EffectId(msg_interned_id::intern::Interned<str>);
+
+fn main() {}
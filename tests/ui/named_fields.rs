@@ -0,0 +1,8 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+pub struct SpellId {
+    name: bevy_ecs::intern::Interned<str>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+#[interned_id(frobnicate)]
+pub struct SpellId(String);
+
+fn main() {}
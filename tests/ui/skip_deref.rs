@@ -0,0 +1,11 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, skip(Deref))]
+pub struct SpellId(msg_interned_id::intern::Interned<str>);
+
+fn takes_str(_: &str) {}
+
+fn main() {
+    takes_str(&SpellId::new("fireball"));
+}
@@ -0,0 +1,7 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, strict(reflect))]
+pub struct SpellId(msg_interned_id::intern::Interned<str>);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+pub union SpellId {
+    raw: u32,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+pub struct SpellId;
+
+fn main() {}
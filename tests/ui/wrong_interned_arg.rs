@@ -0,0 +1,6 @@
+use msg_interned_id::InternedId;
+
+#[derive(InternedId)]
+pub struct SpellId(bevy_ecs::intern::Interned<[u8]>);
+
+fn main() {}
@@ -0,0 +1,148 @@
+//! Tests for `#[interned_id(validate = ...)]` and the generated `try_new`.
+
+use msg_interned_id::{IdError, IdErrorKind, InternedId};
+use std::str::FromStr;
+
+/// Rejects empty strings and anything containing whitespace.
+fn no_whitespace(id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        Err("must not be empty".to_owned())
+    } else if let Some(pos) = id.find(char::is_whitespace) {
+        Err(format!("whitespace at byte {pos}"))
+    } else {
+        Ok(())
+    }
+}
+
+mod validators {
+    /// Validator returning a custom error type.
+    pub fn ascii_only(id: &str) -> Result<(), NotAscii> {
+        if id.is_ascii() { Ok(()) } else { Err(NotAscii) }
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct NotAscii;
+
+    impl std::fmt::Display for NotAscii {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("only ASCII is allowed")
+        }
+    }
+
+    impl std::error::Error for NotAscii {}
+}
+
+/// ID checked by a plain function in scope.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(standalone, validate = no_whitespace, skip(Default))]
+pub struct ModSpellId(msg_interned_id::intern::Interned<str>);
+
+/// ID checked by a function behind a module path, with a custom error type.
+#[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[interned_id(bevy_reflect = "bevy_reflect", validate = validators::ascii_only)]
+pub struct AsciiId(bevy_ecs::intern::Interned<str>);
+
+mod try_new {
+    use super::*;
+
+    #[test]
+    fn test_accepts_valid() {
+        let id = ModSpellId::try_new("fireball").unwrap();
+        assert_eq!(id.as_str(), "fireball");
+        assert_eq!(id, ModSpellId::new("fireball"));
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        let err = ModSpellId::try_new("fire ball").unwrap_err();
+        assert_eq!(err.type_name(), "ModSpellId");
+        assert_eq!(err.value(), "fire ball");
+        assert!(matches!(err.kind(), IdErrorKind::Rejected(_)));
+        assert_eq!(
+            err.to_string(),
+            "invalid ModSpellId \"fire ball\": whitespace at byte 4"
+        );
+    }
+
+    #[test]
+    fn test_custom_error_is_source() {
+        let err = AsciiId::try_new("caf\u{e9}").unwrap_err();
+        let source = std::error::Error::source(&err).unwrap();
+        assert_eq!(
+            source.downcast_ref::<validators::NotAscii>(),
+            Some(&validators::NotAscii)
+        );
+    }
+}
+
+mod conversions {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "invalid ModSpellId \"\": must not be empty")]
+    fn test_new_panics_on_invalid() {
+        let _ = ModSpellId::new("");
+    }
+
+    #[test]
+    #[should_panic(expected = "whitespace at byte 0")]
+    fn test_from_panics_on_invalid() {
+        let _: ModSpellId = " padded".into();
+    }
+
+    #[test]
+    fn test_from_accepts_valid() {
+        let id: ModSpellId = String::from("ice_bolt").into();
+        assert_eq!(id.as_str(), "ice_bolt");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            ModSpellId::from_str("ice_bolt").unwrap(),
+            ModSpellId::new("ice_bolt")
+        );
+        let err: IdError = "ice bolt".parse::<ModSpellId>().unwrap_err();
+        assert_eq!(err.value(), "ice bolt");
+    }
+
+    #[test]
+    fn test_from_str_without_validation_is_infallible() {
+        #[derive(InternedId, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        #[interned_id(standalone)]
+        struct PlainId(msg_interned_id::intern::Interned<str>);
+
+        let id: Result<PlainId, std::convert::Infallible> = "any thing".parse();
+        assert_eq!(id.unwrap().as_str(), "any thing");
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_integration {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_valid() {
+        let id: AsciiId = serde_json::from_str("\"plain\"").unwrap();
+        assert_eq!(id.as_str(), "plain");
+    }
+
+    #[test]
+    fn test_deserialize_reports_error() {
+        let err = serde_json::from_str::<ModSpellId>("\"two words\"").unwrap_err();
+        assert!(err.to_string().contains("invalid ModSpellId"));
+        assert!(err.to_string().contains("whitespace at byte 3"));
+    }
+
+    #[test]
+    fn test_deserialize_error_inside_struct() {
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Loadout {
+            spells: Vec<ModSpellId>,
+        }
+
+        let err = serde_json::from_str::<Loadout>(r#"{"spells": ["ok", ""]}"#).unwrap_err();
+        assert!(err.to_string().contains("must not be empty"));
+    }
+}